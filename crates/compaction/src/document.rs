@@ -147,6 +147,37 @@ impl<I, B> Compact<I, B> for FlattenedDocument<I, B> {
 	}
 }
 
+/// Compacts the given expanded document using an already processed context.
+///
+/// The context can be given either as a [`ProcessedRef`] or a reference to a
+/// [`Processed`] or [`ProcessedOwned`] context, typically obtained once and
+/// reused across documents. It is not processed again: the compaction
+/// algorithm directly starts from the processed active context. The
+/// unprocessed context is embedded in the output, unless it is empty, in
+/// which case the output is the compacted document without any `@context`
+/// entry.
+///
+/// [`ProcessedRef`]: json_ld_context_processing_next::ProcessedRef
+/// [`Processed`]: json_ld_context_processing_next::Processed
+/// [`ProcessedOwned`]: json_ld_context_processing_next::ProcessedOwned
+pub async fn compact_with_processed<'a, N, L>(
+	vocabulary: &'a mut N,
+	document: &'a ExpandedDocument<N::Iri, N::BlankId>,
+	context: impl Into<json_ld_context_processing_next::ProcessedRef<'a, 'a, N::Iri, N::BlankId>>,
+	loader: &'a L,
+	options: crate::Options,
+) -> CompactDocumentResult
+where
+	N: rdf_types::VocabularyMut,
+	N::Iri: Clone + Hash + Eq,
+	N::BlankId: Clone + Hash + Eq,
+	L: Loader,
+{
+	document
+		.compact_full(vocabulary, context.into(), loader, options)
+		.await
+}
+
 impl EmbedContext for json_syntax::Value {
 	fn embed_context<N>(
 		&mut self,
@@ -200,3 +231,518 @@ impl EmbedContext for json_syntax::Value {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_ld_context_processing_next::{Process, ProcessedOwned};
+	use json_ld_core_next::NoLoader;
+	use json_ld_expansion_next::Expand;
+	use json_ld_syntax_next::{Parse, TryFromJson};
+	use json_syntax::Value;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	async fn process(json: &str) -> ProcessedOwned<iref::IriBuf, rdf_types::BlankIdBuf> {
+		let (json, _) = Value::parse_str(json).unwrap();
+		json_ld_syntax_next::context::Context::try_from_json(json)
+			.unwrap()
+			.process(no_vocabulary_mut(), &NoLoader, None)
+			.await
+			.unwrap()
+			.into_owned()
+	}
+
+	async fn expand(json: &str) -> ExpandedDocument {
+		let (json, _) = Value::parse_str(json).unwrap();
+		json.expand(&NoLoader).await.unwrap()
+	}
+
+	#[async_std::test]
+	async fn aliased_properties() {
+		let context = process(
+			r#"{
+				"name": "http://schema.org/name",
+				"email": "http://schema.org/email"
+			}"#,
+		)
+		.await;
+		let document = expand(
+			r#"{
+				"http://schema.org/name": "John Smith",
+				"http://schema.org/email": "john.smith@example.org"
+			}"#,
+		)
+		.await;
+
+		let compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+
+		let object = compacted.as_object().unwrap();
+		assert!(object.get_unique("@context").unwrap().is_some());
+		assert_eq!(
+			object.get_unique("name").unwrap().unwrap().as_str(),
+			Some("John Smith")
+		);
+		assert_eq!(
+			object.get_unique("email").unwrap().unwrap().as_str(),
+			Some("john.smith@example.org")
+		)
+	}
+
+	#[async_std::test]
+	async fn empty_context() {
+		let context = process("{}").await;
+		let document = expand(r#"{ "http://schema.org/name": "John Smith" }"#).await;
+
+		let compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			context.as_ref(),
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+
+		let (expected, _) =
+			Value::parse_str(r#"{ "http://schema.org/name": "John Smith" }"#).unwrap();
+		assert_eq!(compacted, expected);
+	}
+
+	#[async_std::test]
+	async fn reverse_properties() {
+		let context = process(
+			r#"{
+				"knownBy": { "@reverse": "http://example.org/knows" },
+				"likes": "http://example.org/likes"
+			}"#,
+		)
+		.await;
+		let document = expand(
+			r#"{
+				"@id": "http://example.org/a",
+				"@reverse": {
+					"http://example.org/knows": { "@id": "http://example.org/b" },
+					"http://example.org/likes": { "@id": "http://example.org/c" }
+				}
+			}"#,
+		)
+		.await;
+
+		let mut compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"@id": "http://example.org/a",
+				"knownBy": { "@id": "http://example.org/b" },
+				"@reverse": {
+					"likes": { "@id": "http://example.org/c" }
+				}
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(compacted, expected)
+	}
+
+	/// The shortest compact IRI is selected, whatever the order in which the
+	/// prefix definitions are visited.
+	#[async_std::test]
+	async fn shortest_compact_iri() {
+		let context = process(
+			r#"{
+				"a": "http://example.org/",
+				"b": "http://example.org/",
+				"c": "http://example.org/",
+				"d": "http://example.org/",
+				"e": "http://example.org/",
+				"z": "http://example.org/vocab/"
+			}"#,
+		)
+		.await;
+		let document = expand(r#"{ "http://example.org/vocab/x": "value" }"#).await;
+
+		let compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+
+		let object = compacted.as_object().unwrap();
+		assert_eq!(
+			object.get_unique("z:x").unwrap().unwrap().as_str(),
+			Some("value")
+		)
+	}
+
+	const NESTED_CONTEXT: &str = r#"{
+		"details": "@nest",
+		"name": { "@id": "http://schema.org/name", "@nest": "details" },
+		"email": { "@id": "http://schema.org/email", "@nest": "details" }
+	}"#;
+
+	async fn compact_nested(document: &ExpandedDocument) -> Value {
+		let context = process(NESTED_CONTEXT).await;
+		let mut compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+
+		compacted.as_object_mut().unwrap().remove("@context");
+		compacted
+	}
+
+	#[async_std::test]
+	async fn nested_properties() {
+		let document = expand(
+			r#"{
+				"http://schema.org/name": "John Smith",
+				"http://schema.org/email": "john.smith@example.org",
+				"http://schema.org/url": "http://example.org/john"
+			}"#,
+		)
+		.await;
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"details": {
+					"name": "John Smith",
+					"email": "john.smith@example.org"
+				},
+				"http://schema.org/url": "http://example.org/john"
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compact_nested(&document).await, expected)
+	}
+
+	#[async_std::test]
+	async fn nest_within_nest() {
+		let document = expand(&format!(
+			r#"{{
+				"@context": {NESTED_CONTEXT},
+				"details": {{
+					"name": "John Smith",
+					"details": {{ "email": "john.smith@example.org" }}
+				}}
+			}}"#
+		))
+		.await;
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"details": {
+					"name": "John Smith",
+					"email": "john.smith@example.org"
+				}
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compact_nested(&document).await, expected)
+	}
+
+	#[async_std::test]
+	async fn language_map() {
+		let context = process(
+			r#"{
+				"label": { "@id": "http://www.w3.org/2000/01/rdf-schema#label", "@container": "@language" }
+			}"#,
+		)
+		.await;
+		let document = expand(
+			r#"{
+				"http://www.w3.org/2000/01/rdf-schema#label": [
+					{ "@value": "Hello", "@language": "en" },
+					{ "@value": "Hi", "@language": "en" },
+					{ "@value": "Bonjour", "@language": "fr" },
+					{ "@value": "Ciao" }
+				]
+			}"#,
+		)
+		.await;
+
+		let mut compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"label": {
+					"en": ["Hello", "Hi"],
+					"fr": "Bonjour",
+					"@none": "Ciao"
+				}
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compacted, expected)
+	}
+
+	#[async_std::test]
+	async fn index_map() {
+		let context = process(
+			r#"{
+				"post": { "@id": "http://example.org/post", "@container": "@index" }
+			}"#,
+		)
+		.await;
+		let document = expand(
+			r#"{
+				"http://example.org/post": [
+					{ "@value": "First", "@index": "en" },
+					{ "@value": "Second", "@index": "en" },
+					{ "@value": "Premier", "@index": "fr" },
+					{ "@value": "Unindexed" }
+				]
+			}"#,
+		)
+		.await;
+
+		let mut compacted = compact_with_processed(
+			no_vocabulary_mut(),
+			&document,
+			&context,
+			&NoLoader,
+			crate::Options::default(),
+		)
+		.await
+		.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"post": {
+					"en": ["First", "Second"],
+					"fr": "Premier",
+					"@none": "Unindexed"
+				}
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compacted, expected)
+	}
+
+	async fn compact_index_map(values: &str, options: crate::Options) -> Value {
+		let context = process(
+			r#"{
+				"post": { "@id": "http://example.org/post", "@container": "@index" }
+			}"#,
+		)
+		.await;
+		let document = expand(&format!(r#"{{ "http://example.org/post": {values} }}"#)).await;
+
+		let mut compacted =
+			compact_with_processed(no_vocabulary_mut(), &document, &context, &NoLoader, options)
+				.await
+				.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+		compacted
+	}
+
+	const MIXED_INDEXES: &str = r#"[
+		{ "@value": "First", "@index": "en" },
+		{ "@value": "Unindexed" }
+	]"#;
+
+	const NO_INDEXES: &str = r#"[{ "@value": "A" }, { "@value": "B" }]"#;
+
+	#[async_std::test]
+	async fn custom_none_key() {
+		let options = crate::Options {
+			none_key: crate::NoneKey::Custom("other"),
+			..Default::default()
+		};
+
+		let (expected, _) =
+			Value::parse_str(r#"{ "post": { "en": "First", "other": "Unindexed" } }"#).unwrap();
+		assert_eq!(compact_index_map(MIXED_INDEXES, options).await, expected);
+
+		let (expected, _) = Value::parse_str(r#"{ "post": { "other": ["A", "B"] } }"#).unwrap();
+		assert_eq!(compact_index_map(NO_INDEXES, options).await, expected)
+	}
+
+	#[async_std::test]
+	async fn dropped_none_key() {
+		let options = crate::Options {
+			none_key: crate::NoneKey::Drop,
+			..Default::default()
+		};
+
+		let (expected, _) = Value::parse_str(r#"{ "post": { "en": "First" } }"#).unwrap();
+		assert_eq!(compact_index_map(MIXED_INDEXES, options).await, expected);
+
+		let (expected, _) = Value::parse_str(r#"{}"#).unwrap();
+		assert_eq!(compact_index_map(NO_INDEXES, options).await, expected)
+	}
+
+	#[async_std::test]
+	async fn single_none_key() {
+		let (expected, _) = Value::parse_str(r#"{ "post": { "@none": ["A", "B"] } }"#).unwrap();
+		assert_eq!(
+			compact_index_map(NO_INDEXES, crate::Options::default()).await,
+			expected
+		)
+	}
+
+	const VOCAB_CONTEXT: &str = r#"{
+		"@vocab": "http://schema.org/",
+		"fullName": "http://schema.org/name"
+	}"#;
+
+	async fn compact_vocab(options: crate::Options) -> Value {
+		let context = process(VOCAB_CONTEXT).await;
+		let document = expand(&format!(
+			r#"{{
+				"@context": {VOCAB_CONTEXT},
+				"fullName": "John Smith",
+				"email": "john.smith@example.org"
+			}}"#
+		))
+		.await;
+
+		let iris: Vec<_> = document.iris().map(|iri| iri.as_str()).collect();
+		assert!(iris.contains(&"http://schema.org/name"));
+		assert!(iris.contains(&"http://schema.org/email"));
+
+		let mut compacted =
+			compact_with_processed(no_vocabulary_mut(), &document, &context, &NoLoader, options)
+				.await
+				.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+		compacted
+	}
+
+	#[async_std::test]
+	async fn vocab_round_trip() {
+		let compacted = compact_vocab(crate::Options::default()).await;
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"fullName": "John Smith",
+				"email": "john.smith@example.org"
+			}"#,
+		)
+		.unwrap();
+
+		// The explicit `fullName` definition wins over the `name` suffix.
+		assert_eq!(compacted, expected)
+	}
+
+	#[async_std::test]
+	async fn vocab_disabled() {
+		let compacted = compact_vocab(crate::Options {
+			use_vocab: false,
+			..Default::default()
+		})
+		.await;
+
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"fullName": "John Smith",
+				"http://schema.org/email": "john.smith@example.org"
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compacted, expected)
+	}
+
+	const ARRAYS_CONTEXT: &str = r#"{
+		"@vocab": "http://example.org/",
+		"tags": { "@container": "@set" },
+		"steps": { "@container": "@list" }
+	}"#;
+
+	async fn compact_arrays(options: crate::Options) -> Value {
+		let context = process(ARRAYS_CONTEXT).await;
+		let document = expand(&format!(
+			r#"{{
+				"@context": {ARRAYS_CONTEXT},
+				"name": ["Alice"],
+				"tags": "a",
+				"steps": ["first"]
+			}}"#
+		))
+		.await;
+
+		let mut compacted =
+			compact_with_processed(no_vocabulary_mut(), &document, &context, &NoLoader, options)
+				.await
+				.unwrap();
+		compacted.as_object_mut().unwrap().remove("@context");
+		compacted
+	}
+
+	#[async_std::test]
+	async fn compact_arrays_enabled() {
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"name": "Alice",
+				"tags": ["a"],
+				"steps": ["first"]
+			}"#,
+		)
+		.unwrap();
+
+		// Only the plain term is unwrapped.
+		assert_eq!(compact_arrays(crate::Options::default()).await, expected)
+	}
+
+	#[async_std::test]
+	async fn compact_arrays_disabled() {
+		let compacted = compact_arrays(crate::Options {
+			compact_arrays: false,
+			..Default::default()
+		})
+		.await;
+
+		// The top level array is kept too, hence wrapped in `@graph`.
+		let (expected, _) = Value::parse_str(
+			r#"{
+				"@graph": [{
+					"name": ["Alice"],
+					"tags": ["a"],
+					"steps": ["first"]
+				}]
+			}"#,
+		)
+		.unwrap();
+
+		assert_eq!(compacted, expected)
+	}
+}
@@ -393,8 +393,9 @@ where
 		}
 
 		// At this point, there is no simple term that var can be compacted to.
-		// If vocab is true and active context has a vocabulary mapping:
-		if let Some(vocab_mapping) = active_context.vocabulary() {
+		// If vocab is true and active context has a vocabulary mapping (unless
+		// disabled by the `use_vocab` option):
+		if let Some(vocab_mapping) = active_context.vocabulary().filter(|_| options.use_vocab) {
 			// If var begins with the vocabulary mapping's value but is longer, then initialize
 			// suffix to the substring of var that does not match. If suffix does not have a term
 			// definition in active context, then return suffix.
@@ -446,7 +447,8 @@ where
 						// and value is null, set compact IRI to candidate.
 						let candidate_def = active_context.get(candidate.as_str());
 						if (compact_iri.is_empty()
-							|| (candidate.len() <= compact_iri.len() && candidate < compact_iri))
+							|| candidate.len() < compact_iri.len()
+							|| (candidate.len() == compact_iri.len() && candidate < compact_iri))
 							&& (candidate_def.is_none()
 								|| (candidate_def.is_some()
 									&& (candidate_def.and_then(|def| def.value()) == Some(var))
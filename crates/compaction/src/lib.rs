@@ -81,6 +81,32 @@ pub struct Options {
 	/// If set to `true`, properties are processed by lexical order.
 	/// If `false`, order is not considered in processing.
 	pub ordered: bool,
+
+	/// If set to `true`, IRIs starting with the active context's vocabulary
+	/// mapping (`@vocab`) are compacted into the bare suffix, provided the
+	/// suffix has no term definition of its own.
+	/// If `false`, the vocabulary mapping is ignored and such IRIs are
+	/// compacted as if no `@vocab` was defined.
+	pub use_vocab: bool,
+
+	/// Key under which map containers (`@index`, `@language`, `@id`, `@type`)
+	/// hold the values that have no map key, such as values without an
+	/// index in an index map.
+	pub none_key: NoneKey,
+}
+
+/// Map key given to values without one in map containers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NoneKey {
+	/// IRI compacted `@none` keyword, or one of its aliases.
+	#[default]
+	Keyword,
+
+	/// Custom key.
+	Custom(&'static str),
+
+	/// The values are dropped.
+	Drop,
 }
 
 impl Options {
@@ -118,6 +144,8 @@ impl Default for Options {
 			compact_to_relative: true,
 			compact_arrays: true,
 			ordered: false,
+			use_vocab: true,
+			none_key: NoneKey::default(),
 		}
 	}
 }
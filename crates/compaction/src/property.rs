@@ -1,6 +1,6 @@
 use crate::{
 	add_value, compact_collection_with, compact_iri, compact_iri_with, compact_key, value_value,
-	CompactFragment, CompactIndexedFragment, Error, Options,
+	CompactFragment, CompactIndexedFragment, Error, NoneKey, Options,
 };
 
 use json_ld_core_next::{
@@ -12,6 +12,32 @@ use json_ld_syntax_next::Keyword;
 use rdf_types::VocabularyMut;
 use std::hash::Hash;
 
+/// Returns the map key of values without one in map containers, according
+/// to the `none_key` option, or `None` if they must be dropped.
+fn none_key<N>(
+	vocabulary: &N,
+	active_context: &Context<N::Iri, N::BlankId>,
+	options: Options,
+) -> Result<Option<String>, Error>
+where
+	N: VocabularyMut,
+	N::Iri: Clone + Hash + Eq,
+	N::BlankId: Clone + Hash + Eq,
+{
+	match options.none_key {
+		NoneKey::Keyword => Ok(compact_iri(
+			vocabulary,
+			active_context,
+			&Term::Keyword(Keyword::None),
+			true,
+			false,
+			options,
+		)?),
+		NoneKey::Custom(key) => Ok(Some(key.to_owned())),
+		NoneKey::Drop => Ok(None),
+	}
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn compact_property_list<N, L>(
 	vocabulary: &mut N,
@@ -155,13 +181,27 @@ where
 		// `expanded_item` or @none if no such value exists
 		// with `vocab` set to false if there is an @id entry in
 		// `expanded_item`.
-		let (id_value, vocab) = match &node.id {
-			Some(entry) => (entry.clone().into_term(), false),
-			None => (Term::Keyword(Keyword::None), true),
-		};
+		let map_key = match &node.id {
+			Some(entry) => compact_iri(
+				vocabulary,
+				active_context,
+				&entry.clone().into_term(),
+				false,
+				false,
+				options,
+			)?
+			.unwrap(),
+			None => match none_key(vocabulary, active_context, options)? {
+				Some(key) => key,
+				None => {
+					if map_object.is_empty() {
+						nest_result.remove(item_active_property);
+					}
 
-		let map_key =
-			compact_iri(vocabulary, active_context, &id_value, vocab, false, options)?.unwrap();
+					return Ok(());
+				}
+			},
+		};
 
 		// Use `add_value` to add `compacted_item` to
 		// the `map_key` entry in `map_object` using `as_array`.
@@ -194,7 +234,18 @@ where
 
 		// Initialize `map_key` the value of @index in `expanded_item`
 		// or @none, if no such value exists.
-		let map_key = expanded_index.unwrap_or("@none");
+		let map_key = match (expanded_index, options.none_key) {
+			(Some(index), _) => index,
+			(None, NoneKey::Keyword) => "@none",
+			(None, NoneKey::Custom(key)) => key,
+			(None, NoneKey::Drop) => {
+				if map_object.is_empty() {
+					nest_result.remove(item_active_property);
+				}
+
+				return Ok(());
+			}
+		};
 
 		// Use `add_value` to add `compacted_item` to
 		// the `map_key` entry in `map_object` using `as_array`.
@@ -724,23 +775,24 @@ where
 						// If `map_key` is null, set it to the result of
 						// IRI compacting @none.
 						let map_key = match map_key {
-							Some(key) => key,
-							None => {
-								let key = compact_iri(
-									vocabulary,
-									active_context,
-									&Term::Keyword(Keyword::None),
-									true,
-									false,
-									options,
-								)?;
-								key.unwrap()
-							}
+							Some(key) => Some(key),
+							None => none_key(vocabulary, active_context, options)?,
 						};
 
 						// Use `add_value` to add `compacted_item` to
 						// the `map_key` entry in `map_object` using `as_array`.
-						add_value(map_object, &map_key, compacted_item, as_array)
+						match map_key {
+							Some(map_key) => {
+								add_value(map_object, &map_key, compacted_item, as_array)
+							}
+							None => {
+								// The value is dropped, along with the map if
+								// it is left empty.
+								if map_object.is_empty() {
+									nest_result.remove(item_active_property.as_str());
+								}
+							}
+						}
 					} else {
 						// Otherwise, use `add_value` to add `compacted_item` to the
 						// `item_active_property` entry in `nest_result` using `as_array`.
@@ -156,7 +156,9 @@ where
 								if definition.modulo_protected_field()
 									!= previous_definition.modulo_protected_field()
 								{
-									return Err(Error::ProtectedTermRedefinition);
+									return Err(Error::ProtectedTermRedefinition(
+										Keyword::Type.into_str().to_owned(),
+									));
 								}
 
 								// Set `definition` to `previous definition` to retain the value of
@@ -759,7 +761,9 @@ where
 								if definition.modulo_protected_field()
 									!= previous_definition.modulo_protected_field()
 								{
-									return Err(Error::ProtectedTermRedefinition);
+									return Err(Error::ProtectedTermRedefinition(
+										term.as_str().to_owned(),
+									));
 								}
 
 								// Set `definition` to `previous definition` to retain the value of
@@ -786,3 +790,76 @@ where
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::context, Error, Options, Process};
+	use iref::IriBuf;
+	use json_ld_core_next::{Context, NoLoader};
+	use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+
+	#[async_std::test]
+	async fn protected_term_redefinition() {
+		let protected = context(r#"{ "@protected": true, "name": "http://schema.org/name" }"#);
+		let active: Context<IriBuf, BlankIdBuf> = protected
+			.process(no_vocabulary_mut(), &NoLoader, None)
+			.await
+			.unwrap()
+			.into_processed();
+
+		let redefinition = context(r#"{ "name": "http://example.org/name" }"#);
+		let error = redefinition
+			.process_with(
+				no_vocabulary_mut(),
+				&active,
+				&NoLoader,
+				None,
+				Options::default(),
+			)
+			.await
+			.unwrap_err();
+
+		match error {
+			Error::ProtectedTermRedefinition(term) => assert_eq!(term, "name"),
+			other => panic!("unexpected error: {other:?}"),
+		}
+	}
+
+	#[async_std::test]
+	async fn protected_terms() {
+		let local = context(
+			r#"{
+				"@protected": true,
+				"name": "http://schema.org/name",
+				"email": { "@id": "http://schema.org/email", "@protected": false }
+			}"#,
+		);
+		let active: Context<IriBuf, BlankIdBuf> = local
+			.process(no_vocabulary_mut(), &NoLoader, None)
+			.await
+			.unwrap()
+			.into_processed();
+
+		assert!(active.is_protected("name"));
+		assert!(!active.is_protected("email"));
+		assert!(!active.is_protected("undefined"));
+
+		let local = context(
+			r#"{
+				"name": { "@id": "http://schema.org/name", "@protected": true },
+				"email": "http://schema.org/email"
+			}"#,
+		);
+		let active: Context<IriBuf, BlankIdBuf> = local
+			.process(no_vocabulary_mut(), &NoLoader, None)
+			.await
+			.unwrap()
+			.into_processed();
+
+		assert!(active.is_protected("name"));
+		assert!(!active.is_protected("email"));
+
+		let terms: Vec<_> = active.protected_terms().map(|term| term.as_str()).collect();
+		assert_eq!(terms, ["name"])
+	}
+}
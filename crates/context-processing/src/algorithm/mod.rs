@@ -412,3 +412,79 @@ where
 
 	Ok(Processed::new(local_context, result))
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::context, Error, Process};
+	use iref::{Iri, IriBuf};
+	use json_ld_core_next::{Context, RemoteDocument};
+	use json_ld_syntax_next::{Parse, Value};
+	use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+	use std::collections::HashMap;
+
+	/// Loader serving the given context document at `http://example.org/base`.
+	fn loader(json: &str) -> HashMap<IriBuf, RemoteDocument> {
+		let url = IriBuf::new("http://example.org/base".to_owned()).unwrap();
+		let (json, _) = Value::parse_str(json).unwrap();
+		let document = RemoteDocument::new(Some(url.clone()), None, json);
+		HashMap::from([(url, document)])
+	}
+
+	fn term_iri<'a>(context: &'a Context<IriBuf, BlankIdBuf>, term: &str) -> Option<&'a Iri> {
+		Some(context.get(term)?.value()?.as_iri()?.as_iri())
+	}
+
+	#[async_std::test]
+	async fn import() {
+		let loader = loader(
+			r#"{
+				"@context": {
+					"@vocab": "http://example.org/vocab#",
+					"name": "http://example.org/vocab#name",
+					"knows": "http://example.org/vocab#knows"
+				}
+			}"#,
+		);
+
+		let local = context(
+			r#"{
+				"@version": 1.1,
+				"@import": "http://example.org/base",
+				"name": "http://schema.org/name"
+			}"#,
+		);
+		let active: Context<IriBuf, BlankIdBuf> = local
+			.process(no_vocabulary_mut(), &loader, None)
+			.await
+			.unwrap()
+			.into_processed();
+
+		assert_eq!(
+			term_iri(&active, "name").map(Iri::as_str),
+			Some("http://schema.org/name")
+		);
+		assert_eq!(
+			term_iri(&active, "knows").map(Iri::as_str),
+			Some("http://example.org/vocab#knows")
+		);
+		assert_eq!(
+			active
+				.vocabulary()
+				.and_then(|vocab| vocab.as_iri())
+				.map(IriBuf::as_str),
+			Some("http://example.org/vocab#")
+		)
+	}
+
+	#[async_std::test]
+	async fn import_array() {
+		let loader = loader(r#"{ "@context": [{ "name": "http://example.org/vocab#name" }] }"#);
+		let local = context(r#"{ "@version": 1.1, "@import": "http://example.org/base" }"#);
+		let error = local
+			.process(no_vocabulary_mut(), &loader, None)
+			.await
+			.unwrap_err();
+
+		assert!(matches!(error, Error::InvalidRemoteContext))
+	}
+}
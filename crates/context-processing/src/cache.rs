@@ -0,0 +1,311 @@
+use std::{
+	collections::HashMap,
+	hash::{BuildHasher, Hash, Hasher},
+};
+
+use iref::IriBuf;
+use json_ld_core_next::{object::node::multiset::DeterministicHasherBuilder, Context, Loader};
+use json_ld_syntax_next::context::{ContextEntry, Definition};
+use rdf_types::{BlankIdBuf, VocabularyMut};
+
+use crate::{Error, Options, Process, ProcessedOwned, WarningHandler};
+
+/// Cache of processed contexts.
+///
+/// Memoizes the result of processing a local context from an empty active
+/// context (whose base IRI is the given base URL). Entries are keyed by a
+/// hash of the unprocessed context, the base URL and the processing options.
+/// See [`process_with_cache`].
+pub struct ProcessedCache<T = IriBuf, B = BlankIdBuf> {
+	entries: HashMap<u64, Vec<CacheEntry<T, B>>>,
+}
+
+struct CacheEntry<T, B> {
+	base_url: Option<T>,
+	options: Options,
+	processed: ProcessedOwned<T, B>,
+}
+
+impl<T, B> CacheEntry<T, B> {
+	fn matches(
+		&self,
+		context: &json_ld_syntax_next::context::Context,
+		base_url: Option<&T>,
+		options: Options,
+	) -> bool
+	where
+		T: PartialEq,
+	{
+		self.options == options
+			&& self.base_url.as_ref() == base_url
+			&& self.processed.unprocessed() == context
+	}
+}
+
+impl<T, B> Default for ProcessedCache<T, B> {
+	fn default() -> Self {
+		Self {
+			entries: HashMap::new(),
+		}
+	}
+}
+
+impl<T, B> ProcessedCache<T, B> {
+	/// Creates a new empty cache.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of cached contexts.
+	pub fn len(&self) -> usize {
+		self.entries.values().map(Vec::len).sum()
+	}
+
+	/// Checks if the cache is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Removes every cached context.
+	pub fn clear(&mut self) {
+		self.entries.clear()
+	}
+
+	/// Returns the cached result of processing `context` with the given
+	/// `base_url` and `options`, if any.
+	pub fn get(
+		&self,
+		context: &json_ld_syntax_next::context::Context,
+		base_url: Option<&T>,
+		options: Options,
+	) -> Option<&ProcessedOwned<T, B>>
+	where
+		T: Eq + Hash,
+	{
+		self.entries
+			.get(&cache_key(context, base_url, options))?
+			.iter()
+			.find(|e| e.matches(context, base_url, options))
+			.map(|e| &e.processed)
+	}
+}
+
+fn cache_key<T: Hash>(
+	context: &json_ld_syntax_next::context::Context,
+	base_url: Option<&T>,
+	options: Options,
+) -> u64 {
+	let mut hasher = DeterministicHasherBuilder.build_hasher();
+	hash_context(context, &mut hasher);
+	base_url.hash(&mut hasher);
+	options.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// Hashes the given context without cloning it.
+///
+/// Only the outline of the context is hashed (entry kinds, IRI references,
+/// base, imports and defined terms), which is enough to spread contexts among
+/// buckets. Contexts sharing a hash are compared in full.
+fn hash_context(context: &json_ld_syntax_next::context::Context, hasher: &mut impl Hasher) {
+	hasher.write_usize(context.len());
+	for entry in context {
+		match entry {
+			ContextEntry::Null => hasher.write_u8(0),
+			ContextEntry::IriRef(iri_ref) => {
+				hasher.write_u8(1);
+				iri_ref.as_str().hash(hasher)
+			}
+			ContextEntry::Definition(definition) => {
+				hasher.write_u8(2);
+				hash_definition(definition, hasher)
+			}
+		}
+	}
+}
+
+fn hash_definition(definition: &Definition, hasher: &mut impl Hasher) {
+	definition
+		.base
+		.as_ref()
+		.map(|base| base.as_ref().map(|iri_ref| iri_ref.as_str()))
+		.hash(hasher);
+	definition
+		.import
+		.as_ref()
+		.map(|iri_ref| iri_ref.as_str())
+		.hash(hasher);
+
+	// Bindings are compared regardless of their order, so are their keys.
+	let keys = definition.bindings.iter().fold(0u64, |sum, (key, _)| {
+		sum.wrapping_add(DeterministicHasherBuilder.hash_one(key.as_str()))
+	});
+	hasher.write_usize(definition.bindings.len());
+	hasher.write_u64(keys)
+}
+
+/// Process the given local context, using the `cache` to avoid processing
+/// the same context twice.
+///
+/// The context is processed from an empty active context whose base IRI is
+/// `base_url`. On a cache hit, the previously processed context is returned
+/// without calling the `loader` or emitting any warning. Two contexts sharing
+/// the same hash are told apart by comparing the unprocessed contexts.
+pub async fn process_with_cache<'c, N, L, W>(
+	cache: &'c mut ProcessedCache<N::Iri, N::BlankId>,
+	context: &json_ld_syntax_next::context::Context,
+	vocabulary: &mut N,
+	loader: &L,
+	base_url: Option<N::Iri>,
+	options: Options,
+	warnings: W,
+) -> Result<&'c ProcessedOwned<N::Iri, N::BlankId>, Error>
+where
+	N: VocabularyMut,
+	N::Iri: Clone + Eq + Hash,
+	N::BlankId: Clone + PartialEq,
+	L: Loader,
+	W: WarningHandler<N>,
+{
+	let key = cache_key(context, base_url.as_ref(), options);
+
+	// The bucket is only created once the context has been processed, so
+	// that failures leave the cache untouched.
+	let cached = cache.entries.get(&key).and_then(|bucket| {
+		bucket
+			.iter()
+			.position(|e| e.matches(context, base_url.as_ref(), options))
+	});
+
+	if let Some(i) = cached {
+		return Ok(&cache.entries[&key][i].processed);
+	}
+
+	let active_context = Context::new(base_url.clone());
+	let processed = context
+		.process_full(
+			vocabulary,
+			&active_context,
+			loader,
+			base_url.clone(),
+			options,
+			warnings,
+		)
+		.await?
+		.into_owned();
+
+	let bucket = cache.entries.entry(key).or_default();
+	bucket.push(CacheEntry {
+		base_url,
+		options,
+		processed,
+	});
+
+	Ok(&bucket.last().unwrap().processed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::context;
+	use json_ld_core_next::NoLoader;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	#[async_std::test]
+	async fn cache_hit() {
+		let mut cache = ProcessedCache::new();
+		let local = context(r#"{ "name": "http://schema.org/name" }"#);
+
+		let first: *const _ = process_with_cache(
+			&mut cache,
+			&local,
+			no_vocabulary_mut(),
+			&NoLoader,
+			None,
+			Options::default(),
+			(),
+		)
+		.await
+		.unwrap();
+
+		let second: *const _ = process_with_cache(
+			&mut cache,
+			&local,
+			no_vocabulary_mut(),
+			&NoLoader,
+			None,
+			Options::default(),
+			(),
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(cache.len(), 1);
+		assert!(std::ptr::eq(first, second))
+	}
+
+	#[async_std::test]
+	async fn distinct_base_iris() {
+		let mut cache = ProcessedCache::new();
+		let local = context(r#"{ "@base": "foo/" }"#);
+		let a = IriBuf::new("http://example.org/a/".to_owned()).unwrap();
+		let b = IriBuf::new("http://example.org/b/".to_owned()).unwrap();
+
+		for base in [&a, &b] {
+			let processed = process_with_cache(
+				&mut cache,
+				&local,
+				no_vocabulary_mut(),
+				&NoLoader,
+				Some(base.clone()),
+				Options::default(),
+				(),
+			)
+			.await
+			.unwrap();
+
+			let expected = format!("{base}foo/");
+			assert_eq!(
+				processed.processed().base_iri().map(IriBuf::as_str),
+				Some(expected.as_str())
+			);
+		}
+
+		assert_eq!(cache.len(), 2);
+		assert!(cache.get(&local, Some(&a), Options::default()).is_some());
+		assert!(cache.get(&local, Some(&b), Options::default()).is_some())
+	}
+
+	#[async_std::test]
+	async fn failure_is_not_cached() {
+		let mut cache = ProcessedCache::new();
+		let local = context(r#""http://example.org/context.jsonld""#);
+
+		let result = process_with_cache(
+			&mut cache,
+			&local,
+			no_vocabulary_mut(),
+			&NoLoader,
+			None,
+			Options::default(),
+			(),
+		)
+		.await;
+
+		assert!(result.is_err());
+		assert!(cache.entries.is_empty())
+	}
+
+	#[test]
+	fn key_ignores_binding_order() {
+		let a =
+			context(r#"{ "name": "http://schema.org/name", "knows": "http://schema.org/knows" }"#);
+		let b =
+			context(r#"{ "knows": "http://schema.org/knows", "name": "http://schema.org/name" }"#);
+		assert_eq!(a, b);
+		assert_eq!(
+			cache_key::<IriBuf>(&a, None, Options::default()),
+			cache_key::<IriBuf>(&b, None, Options::default())
+		)
+	}
+}
@@ -7,9 +7,13 @@ use rdf_types::VocabularyMut;
 use std::{fmt, hash::Hash};
 
 pub mod algorithm;
+mod cache;
 mod processed;
 mod stack;
+#[cfg(test)]
+mod test_utils;
 
+pub use cache::*;
 pub use processed::*;
 pub use stack::ProcessingStack;
 
@@ -97,8 +101,8 @@ pub enum Error {
 	#[error("Invalid scoped context")]
 	InvalidScopedContext,
 
-	#[error("Protected term redefinition")]
-	ProtectedTermRedefinition,
+	#[error("Protected term `{0}` redefinition")]
+	ProtectedTermRedefinition(String),
 
 	#[error(transparent)]
 	ContextLoadingFailed(#[from] LoadError),
@@ -137,7 +141,7 @@ impl Error {
 			Self::InvalidKeywordAlias => ErrorCode::InvalidKeywordAlias,
 			Self::InvalidContainerMapping => ErrorCode::InvalidContainerMapping,
 			Self::InvalidScopedContext => ErrorCode::InvalidScopedContext,
-			Self::ProtectedTermRedefinition => ErrorCode::ProtectedTermRedefinition,
+			Self::ProtectedTermRedefinition(_) => ErrorCode::ProtectedTermRedefinition,
 			Self::ContextLoadingFailed(_) => ErrorCode::LoadingRemoteContextFailed,
 			Self::ContextExtractionFailed(_) => ErrorCode::LoadingRemoteContextFailed,
 			Self::ForbiddenVocab => ErrorCode::InvalidVocabMapping,
@@ -195,6 +199,68 @@ pub trait Process {
 		.await
 	}
 
+	/// Process the local context with specific options, overriding the base
+	/// IRI of the processed context with `base_override`.
+	///
+	/// The override takes precedence over any `@base` entry of the local
+	/// context, except `@base: null`, which still removes the base IRI. If
+	/// `base_override` is `None`, this is the same as
+	/// [`process_full`](Self::process_full).
+	#[allow(clippy::too_many_arguments)]
+	#[allow(async_fn_in_trait)]
+	async fn process_with_base_override<N, L, W>(
+		&self,
+		vocabulary: &mut N,
+		active_context: &Context<N::Iri, N::BlankId>,
+		loader: &L,
+		base_url: Option<N::Iri>,
+		base_override: Option<N::Iri>,
+		options: Options,
+		warnings: W,
+	) -> Result<Processed<N::Iri, N::BlankId>, Error>
+	where
+		N: VocabularyMut,
+		N::Iri: Clone + Eq + Hash,
+		N::BlankId: Clone + PartialEq,
+		L: Loader,
+		W: WarningHandler<N>,
+	{
+		let Some(base_override) = base_override else {
+			return self
+				.process_full(
+					vocabulary,
+					active_context,
+					loader,
+					base_url,
+					options,
+					warnings,
+				)
+				.await;
+		};
+
+		// Starting from the override ensures the processed context only lacks
+		// a base IRI if it was explicitly removed.
+		let mut active_context = active_context.clone();
+		active_context.set_base_iri(Some(base_override.clone()));
+
+		let mut processed = self
+			.process_full(
+				vocabulary,
+				&active_context,
+				loader,
+				base_url,
+				options,
+				warnings,
+			)
+			.await?;
+
+		if processed.processed.base_iri().is_some() {
+			processed.processed.set_base_iri(Some(base_override))
+		}
+
+		Ok(processed)
+	}
+
 	/// Process the local context with the given initial active context with the default options:
 	/// `is_remote` is `false`, `override_protected` is `false` and `propagate` is `true`.
 	#[allow(async_fn_in_trait)]
@@ -224,7 +290,7 @@ pub trait Process {
 }
 
 /// Options of the Context Processing Algorithm.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Options {
 	/// The processing mode
 	pub processing_mode: ProcessingMode,
@@ -275,3 +341,58 @@ impl Default for Options {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::context;
+	use iref::IriBuf;
+	use json_ld_core_next::NoLoader;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	async fn processed_base(json: &str, base_override: &str) -> Option<IriBuf> {
+		let local = context(json);
+		local
+			.process_with_base_override(
+				no_vocabulary_mut(),
+				&Context::default(),
+				&NoLoader,
+				None,
+				Some(IriBuf::new(base_override.to_owned()).unwrap()),
+				Options::default(),
+				(),
+			)
+			.await
+			.unwrap()
+			.into_processed()
+			.base_iri()
+			.cloned()
+	}
+
+	#[async_std::test]
+	async fn base_override() {
+		let base = "http://example.org/override/";
+
+		for json in [
+			r#"{ "@base": "http://example.org/base/" }"#,
+			r#"{ "@base": "foo/" }"#,
+			"{}",
+		] {
+			assert_eq!(
+				processed_base(json, base)
+					.await
+					.as_ref()
+					.map(IriBuf::as_str),
+				Some(base)
+			)
+		}
+	}
+
+	#[async_std::test]
+	async fn null_base_wins_over_override() {
+		assert_eq!(
+			processed_base(r#"{ "@base": null }"#, "http://example.org/override/").await,
+			None
+		)
+	}
+}
@@ -1,7 +1,7 @@
 use iref::IriBuf;
 use json_ld_core_next::Context;
 use rdf_types::BlankIdBuf;
-use std::ops;
+use std::{fmt, ops};
 
 /// Processed context that also borrows the original, unprocessed, context.
 pub struct Processed<'l, T = IriBuf, B = BlankIdBuf> {
@@ -28,6 +28,29 @@ impl<'l, T, B> Processed<'l, T, B> {
 		self.processed
 	}
 
+	/// Splits this context into the borrowed unprocessed context and the
+	/// processed context.
+	pub fn into_parts(self) -> (&'l json_ld_syntax_next::context::Context, Context<T, B>) {
+		(self.unprocessed, self.processed)
+	}
+
+	/// Builds a processed context from the parts returned by
+	/// [`Self::into_parts`].
+	pub fn from_parts(
+		(unprocessed, processed): (&'l json_ld_syntax_next::context::Context, Context<T, B>),
+	) -> Self {
+		Self::new(unprocessed, processed)
+	}
+
+	/// Transforms the processed context with the given function, keeping
+	/// the borrowed unprocessed context.
+	pub fn map<U, C, F: FnOnce(Context<T, B>) -> Context<U, C>>(self, f: F) -> Processed<'l, U, C> {
+		Processed {
+			unprocessed: self.unprocessed,
+			processed: f(self.processed),
+		}
+	}
+
 	pub fn as_ref(&self) -> ProcessedRef<'l, '_, T, B> {
 		ProcessedRef {
 			unprocessed: self.unprocessed,
@@ -43,6 +66,20 @@ impl<'l, T, B> Processed<'l, T, B> {
 	}
 }
 
+impl<'l, 'a, T, B> From<&'a Processed<'l, T, B>> for ProcessedRef<'l, 'a, T, B> {
+	fn from(value: &'a Processed<'l, T, B>) -> Self {
+		value.as_ref()
+	}
+}
+
+impl<T, B> fmt::Debug for Processed<'_, T, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Processed")
+			.field("unprocessed", self.unprocessed)
+			.finish_non_exhaustive()
+	}
+}
+
 impl<T, B> ops::Deref for Processed<'_, T, B> {
 	type Target = Context<T, B>;
 
@@ -83,7 +120,74 @@ impl<'l, 'a, T, B> ProcessedRef<'l, 'a, T, B> {
 	}
 }
 
+impl<T, B> Clone for ProcessedRef<'_, '_, T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			unprocessed: self.unprocessed,
+			processed: self.processed,
+		}
+	}
+}
+
+impl<T, B> fmt::Debug for ProcessedRef<'_, '_, T, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("ProcessedRef")
+			.field("unprocessed", self.unprocessed)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Mutable reference to a processed context that also borrows the original,
+/// unprocessed, context.
+///
+/// Only the processed context can be modified.
+pub struct ProcessedMut<'l, 'a, T, B> {
+	pub unprocessed: &'l json_ld_syntax_next::context::Context,
+	pub processed: &'a mut Context<T, B>,
+}
+
+impl<'l, 'a, T, B> ProcessedMut<'l, 'a, T, B> {
+	pub fn new(
+		unprocessed: &'l json_ld_syntax_next::context::Context,
+		processed: &'a mut Context<T, B>,
+	) -> Self {
+		Self {
+			unprocessed,
+			processed,
+		}
+	}
+
+	pub fn unprocessed(&self) -> &'l json_ld_syntax_next::context::Context {
+		self.unprocessed
+	}
+
+	pub fn processed(&self) -> &Context<T, B> {
+		self.processed
+	}
+
+	pub fn processed_mut(&mut self) -> &mut Context<T, B> {
+		self.processed
+	}
+
+	/// Reborrows this reference as an immutable reference.
+	pub fn as_ref(&self) -> ProcessedRef<'l, '_, T, B> {
+		ProcessedRef {
+			unprocessed: self.unprocessed,
+			processed: self.processed,
+		}
+	}
+}
+
+impl<T, B> fmt::Debug for ProcessedMut<'_, '_, T, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("ProcessedMut")
+			.field("unprocessed", self.unprocessed)
+			.finish_non_exhaustive()
+	}
+}
+
 /// Processed context that also owns the original, unprocessed, context.
+#[derive(Clone)]
 pub struct ProcessedOwned<T, B> {
 	pub unprocessed: json_ld_syntax_next::context::Context,
 	pub processed: Context<T, B>,
@@ -114,4 +218,116 @@ impl<T, B> ProcessedOwned<T, B> {
 			processed: &self.processed,
 		}
 	}
+
+	/// Returns a mutable reference to the processed context, along with the
+	/// unprocessed context.
+	///
+	/// This can be used to edit the processed context in place, for instance
+	/// to inject a term definition, without processing it again.
+	pub fn as_mut(&mut self) -> ProcessedMut<'_, '_, T, B> {
+		ProcessedMut {
+			unprocessed: &self.unprocessed,
+			processed: &mut self.processed,
+		}
+	}
+}
+
+impl<'a, T, B> From<&'a ProcessedOwned<T, B>> for ProcessedRef<'a, 'a, T, B> {
+	fn from(value: &'a ProcessedOwned<T, B>) -> Self {
+		value.as_ref()
+	}
+}
+
+impl<T, B> fmt::Debug for ProcessedOwned<T, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("ProcessedOwned")
+			.field("unprocessed", &self.unprocessed)
+			.finish_non_exhaustive()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use iref::IriBuf;
+
+	#[test]
+	fn map_keeps_unprocessed() {
+		let unprocessed = json_ld_syntax_next::context::Context::null();
+		let base = IriBuf::new("http://example.org/".to_owned()).unwrap();
+		let processed = Processed::new(&unprocessed, Context::<IriBuf>::new(Some(base)));
+
+		let mapped: Processed<String> = processed.map(|context| {
+			context.map_ids(
+				|iri| iri.as_str().to_owned(),
+				|id| match id {
+					rdf_types::Id::Iri(iri) => rdf_types::Id::Iri(iri.as_str().to_owned()),
+					rdf_types::Id::Blank(b) => rdf_types::Id::Blank(b),
+				},
+			)
+		});
+
+		assert!(std::ptr::eq(mapped.unprocessed(), &unprocessed));
+		assert_eq!(
+			mapped.base_iri().map(String::as_str),
+			Some("http://example.org/")
+		)
+	}
+
+	#[test]
+	fn parts_round_trip() {
+		let unprocessed = json_ld_syntax_next::context::Context::null();
+		let base = IriBuf::new("http://example.org/".to_owned()).unwrap();
+		let processed = Processed::new(&unprocessed, Context::<IriBuf>::new(Some(base.clone())));
+
+		let (u, p) = processed.into_parts();
+		assert!(std::ptr::eq(u, &unprocessed));
+
+		let processed = Processed::from_parts((u, p));
+		assert!(std::ptr::eq(processed.unprocessed(), &unprocessed));
+		assert_eq!(processed.base_iri(), Some(&base))
+	}
+
+	#[test]
+	fn debug_owned() {
+		let unprocessed = json_ld_syntax_next::context::Context::null();
+		let owned: ProcessedOwned<IriBuf, BlankIdBuf> =
+			Processed::new(&unprocessed, Context::new(None)).into_owned();
+
+		let owned = owned.clone();
+		assert_eq!(
+			format!("{owned:?}"),
+			format!("ProcessedOwned {{ unprocessed: {unprocessed:?}, .. }}")
+		);
+
+		let r = owned.as_ref();
+		assert!(format!("{:?}", r.clone()).starts_with("ProcessedRef"))
+	}
+
+	#[test]
+	fn edit_owned() {
+		let unprocessed = json_ld_syntax_next::context::Context::null();
+		let mut owned: ProcessedOwned<IriBuf, BlankIdBuf> =
+			Processed::new(&unprocessed, Context::new(None)).into_owned();
+
+		let iri = IriBuf::new("http://schema.org/name".to_owned()).unwrap();
+		let mut m = owned.as_mut();
+		assert_eq!(m.unprocessed(), &unprocessed);
+		m.processed_mut().set_normal(
+			"name".into(),
+			Some(json_ld_core_next::context::NormalTermDefinition {
+				value: Some(json_ld_core_next::Term::Id(json_ld_core_next::Id::iri(
+					iri.clone(),
+				))),
+				..Default::default()
+			}),
+		);
+
+		let r = owned.as_ref();
+		let definition = r.processed().get_normal("name").unwrap();
+		assert_eq!(
+			definition.value,
+			Some(json_ld_core_next::Term::Id(json_ld_core_next::Id::iri(iri)))
+		)
+	}
 }
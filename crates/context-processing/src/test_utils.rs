@@ -0,0 +1,7 @@
+use json_ld_syntax_next::{context::Context, Parse, TryFromJson, Value};
+
+/// Parses the given JSON-LD context definition.
+pub fn context(json: &str) -> Context {
+	let (json, _) = Value::parse_str(json).unwrap();
+	Context::try_from_json(json).unwrap()
+}
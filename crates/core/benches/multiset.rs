@@ -0,0 +1,43 @@
+//! Compares the quadratic multiset equality with the hash-bucketed one on
+//! large property value sets.
+use json_ld_core_next::{
+	object::{node::Multiset, Literal},
+	Indexed, Object, Value,
+};
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 1000;
+const ROUNDS: u32 = 20;
+
+fn measure(name: &str, mut f: impl FnMut() -> bool) -> Duration {
+	let start = Instant::now();
+	for _ in 0..ROUNDS {
+		assert!(std::hint::black_box(f()))
+	}
+	let elapsed = start.elapsed() / ROUNDS;
+	println!("{name}: {elapsed:?} per comparison ({SIZE} values)");
+	elapsed
+}
+
+fn main() {
+	let values: Vec<Indexed<Object>> = (0..SIZE)
+		.map(|i| {
+			Indexed::none(Object::Value(Value::Literal(
+				Literal::String(format!("value #{i}").into()),
+				None,
+			)))
+		})
+		.collect();
+
+	// Reversed order is the worst case for the quadratic comparison.
+	let a: Multiset<Indexed<Object>> = values.iter().cloned().collect();
+	let b: Multiset<Indexed<Object>> = values.into_iter().rev().collect();
+
+	let quadratic = measure("quadratic", || a == b);
+	let hashed = measure("hashed", || a.hashed_eq(&b));
+
+	println!(
+		"speedup: {:.1}x",
+		quadratic.as_secs_f64() / hashed.as_secs_f64()
+	)
+}
@@ -52,6 +52,13 @@ impl<T, B> Default for Context<T, B> {
 	}
 }
 
+/// Protected term redefinition error.
+///
+/// Returned by [`Context::extend`] when a protected term would be overridden.
+#[derive(Debug, thiserror::Error)]
+#[error("protected term `{0}` redefinition")]
+pub struct ProtectedTermRedefinition(pub String);
+
 pub type DefinitionEntryRef<'a, T = IriBuf, B = BlankIdBuf> = (&'a Key, &'a TermDefinition<T, B>);
 
 impl<T, B> Context<T, B> {
@@ -170,6 +177,29 @@ impl<T, B> Context<T, B> {
 		false
 	}
 
+	/// Checks if the given `term` has a protected definition.
+	///
+	/// The `@protected` entry of a context applies to each of its term
+	/// definitions, unless they have their own `@protected` entry.
+	pub fn is_protected<Q>(&self, term: &Q) -> bool
+	where
+		Key: Borrow<Q>,
+		KeywordType: Borrow<Q>,
+		Q: ?Sized + Hash + Eq,
+	{
+		self.get(term)
+			.is_some_and(|definition| definition.protected())
+	}
+
+	/// Returns an iterator over the terms having a protected definition, in
+	/// no particular order.
+	pub fn protected_terms(&self) -> impl '_ + Iterator<Item = BindingTerm<'_>> {
+		self.definitions()
+			.iter()
+			.filter(|binding| binding.definition().protected())
+			.map(|binding| binding.term())
+	}
+
 	/// Returns the inverse of this context.
 	pub fn inverse(&self) -> &InverseContext<T, B>
 	where
@@ -179,6 +209,140 @@ impl<T, B> Context<T, B> {
 		self.inverse.get_or_init(|| self.into())
 	}
 
+	/// Finds the term, or compact IRI, that best represents the given IRI,
+	/// regardless of any value.
+	///
+	/// As in the IRI compaction algorithm, candidates are considered in this
+	/// order:
+	///   - terms defined with the given IRI mapping, selected with the
+	///     [inverse context](Self::inverse). Only terms without type, language
+	///     or direction mapping (or with a `@none` type mapping) that are not
+	///     reverse properties are considered, since the others only apply to
+	///     specific values;
+	///   - the IRI relative to the vocabulary mapping, if it is not a term;
+	///   - compact IRIs using a prefix definition.
+	///
+	/// Ties are broken by choosing the shortest candidate, then the
+	/// lexicographically least one. Returns `None` if the IRI can only be
+	/// represented as is.
+	pub fn term_for_iri<N>(&self, vocabulary: &N, iri: &T) -> Option<String>
+	where
+		N: Vocabulary<Iri = T, BlankId = B>,
+		T: Clone + Hash + Eq,
+		B: Clone + Hash + Eq,
+	{
+		let var = Term::Id(crate::Id::iri(iri.clone()));
+
+		let selection = inverse::Selection::Type(vec![
+			inverse::TypeSelection::Type(crate::Type::None),
+			inverse::TypeSelection::Any,
+		]);
+		if let Some(term) = self.inverse().select(
+			&var,
+			&[crate::Container::None, crate::Container::Set],
+			&selection,
+		) {
+			return Some(term.as_str().to_owned());
+		}
+
+		let var_str = var.with(vocabulary).as_str();
+
+		if let Some(vocab_mapping) = self.vocabulary() {
+			if let Some(suffix) = var_str.strip_prefix(vocab_mapping.with(vocabulary).as_str()) {
+				if !suffix.is_empty() && self.get(suffix).is_none() {
+					return Some(suffix.to_owned());
+				}
+			}
+		}
+
+		let mut compact_iri: Option<String> = None;
+		for binding in self.definitions() {
+			let definition = binding.definition();
+			let Some(iri_mapping) = definition.value().filter(|_| definition.prefix()) else {
+				continue;
+			};
+
+			let Some(suffix) = var_str.strip_prefix(iri_mapping.with(vocabulary).as_str()) else {
+				continue;
+			};
+
+			if suffix.is_empty() {
+				continue;
+			}
+
+			let candidate = format!("{}:{suffix}", binding.term());
+			let better = match &compact_iri {
+				Some(current) => {
+					candidate.len() < current.len()
+						|| (candidate.len() == current.len() && candidate < *current)
+				}
+				None => true,
+			};
+
+			let available = match self.get(candidate.as_str()) {
+				Some(def) => def.value() == Some(&var),
+				None => true,
+			};
+
+			if better && available {
+				compact_iri = Some(candidate)
+			}
+		}
+
+		compact_iri
+	}
+
+	/// Merges the term definitions of `other` into this context, `other`
+	/// taking precedence.
+	///
+	/// Fails, leaving this context unchanged, if a protected term of this
+	/// context would be overridden by a different definition. As in the
+	/// context processing algorithm, redefining a protected term with the same
+	/// definition is allowed and keeps the term protected.
+	pub fn extend(&mut self, other: &Self) -> Result<(), ProtectedTermRedefinition>
+	where
+		T: Clone + PartialEq,
+		B: Clone + PartialEq,
+	{
+		for binding in other.definitions() {
+			let term = binding.term();
+			if let Some(previous) = self.definitions.get(term.as_str()) {
+				if previous.protected()
+					&& previous.modulo_protected_field()
+						!= binding.definition().modulo_protected_field()
+				{
+					return Err(ProtectedTermRedefinition(term.as_str().to_owned()));
+				}
+			}
+		}
+
+		self.inverse.take();
+		for binding in other.definitions() {
+			// Protected terms are only redefined with the same definition, in
+			// which case the previous definition is kept to retain the
+			// `@protected` flag.
+			if self
+				.definitions
+				.get(binding.term().as_str())
+				.is_some_and(|previous| previous.protected())
+			{
+				continue;
+			}
+
+			match binding {
+				BindingRef::Normal(key, definition) => {
+					self.definitions
+						.set_normal(key.clone(), Some(definition.clone()));
+				}
+				BindingRef::Type(definition) => {
+					self.definitions.set_type(Some(definition.clone()));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Sets the normal definition for the given term `key`.
 	pub fn set_normal(
 		&mut self,
@@ -335,3 +499,111 @@ impl<T: PartialEq, B: PartialEq> PartialEq for Context<T, B> {
 			&& self.previous_context == other.previous_context
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn definition(iri: &str, protected: bool) -> NormalTermDefinition {
+		NormalTermDefinition {
+			value: Some(Term::Id(crate::Id::iri(
+				IriBuf::new(iri.to_owned()).unwrap(),
+			))),
+			protected,
+			..Default::default()
+		}
+	}
+
+	fn value<'a>(context: &'a Context, term: &str) -> &'a str {
+		match context.get_normal(term).unwrap().value.as_ref().unwrap() {
+			Term::Id(id) => id.as_str(),
+			_ => panic!("expected an identifier"),
+		}
+	}
+
+	#[test]
+	fn extend() {
+		let mut context = Context::new(None);
+		context.set_normal("a".into(), Some(definition("http://example.org/a", false)));
+		context.set_normal("b".into(), Some(definition("http://example.org/b", false)));
+
+		let mut other = Context::new(None);
+		other.set_normal("b".into(), Some(definition("http://other.org/b", false)));
+		other.set_normal("c".into(), Some(definition("http://other.org/c", false)));
+
+		context.extend(&other).unwrap();
+		assert_eq!(context.len(), 3);
+		assert_eq!(value(&context, "a"), "http://example.org/a");
+		assert_eq!(value(&context, "b"), "http://other.org/b");
+		assert_eq!(value(&context, "c"), "http://other.org/c")
+	}
+
+	#[test]
+	fn extend_protected() {
+		let mut context = Context::new(None);
+		context.set_normal("a".into(), Some(definition("http://example.org/a", true)));
+
+		// Same definition.
+		let mut same = Context::new(None);
+		same.set_normal("a".into(), Some(definition("http://example.org/a", false)));
+		context.extend(&same).unwrap();
+		assert!(context.get("a").unwrap().protected());
+
+		let mut other = Context::new(None);
+		other.set_normal("a".into(), Some(definition("http://other.org/a", false)));
+		other.set_normal("b".into(), Some(definition("http://other.org/b", false)));
+
+		let ProtectedTermRedefinition(term) = context.extend(&other).unwrap_err();
+		assert_eq!(term, "a");
+		assert_eq!(value(&context, "a"), "http://example.org/a");
+		assert!(!context.contains_term("b"))
+	}
+
+	#[test]
+	fn term_for_iri() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+		let prefix = |s: &str| NormalTermDefinition {
+			prefix: true,
+			..definition(s, false)
+		};
+
+		let mut context = Context::new(None);
+		context.set_normal(
+			"name".into(),
+			Some(definition("http://schema.org/name", false)),
+		);
+		context.set_normal(
+			"n".into(),
+			Some(definition("http://schema.org/name", false)),
+		);
+		context.set_normal("schema".into(), Some(prefix("http://schema.org/")));
+		context.set_normal("a".into(), Some(prefix("http://example.org/")));
+		context.set_normal("z".into(), Some(prefix("http://example.org/vocab/")));
+
+		let term = |context: &Context, s: &str| context.term_for_iri(&(), &iri(s));
+
+		// Shortest term first.
+		assert_eq!(
+			term(&context, "http://schema.org/name").as_deref(),
+			Some("n")
+		);
+
+		// Compact IRIs otherwise, the shortest one being preferred.
+		assert_eq!(
+			term(&context, "http://schema.org/email").as_deref(),
+			Some("schema:email")
+		);
+		assert_eq!(
+			term(&context, "http://example.org/vocab/x").as_deref(),
+			Some("z:x")
+		);
+		assert_eq!(term(&context, "http://other.org/x"), None);
+
+		// The vocabulary mapping comes before compact IRIs.
+		context.set_vocabulary(Some(Term::Id(crate::Id::iri(iri("http://schema.org/")))));
+		assert_eq!(
+			term(&context, "http://schema.org/email").as_deref(),
+			Some("email")
+		)
+	}
+}
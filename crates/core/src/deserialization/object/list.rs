@@ -1,15 +1,22 @@
 use linked_data_next::{
-	LinkedData, LinkedDataGraph, LinkedDataPredicateObjects, LinkedDataResource, LinkedDataSubject,
-	ResourceInterpretation,
+	CowRdfTerm, LinkedData, LinkedDataGraph, LinkedDataPredicateObjects, LinkedDataResource,
+	LinkedDataSubject, ResourceInterpretation,
 };
-use rdf_types::{vocabulary::IriVocabularyMut, Interpretation, Vocabulary};
+use rdf_types::{vocabulary::IriVocabularyMut, Id, Interpretation, Term, Vocabulary};
 
 use crate::{
 	object::List,
-	rdf::{RDF_FIRST, RDF_REST},
+	rdf::{RDF_FIRST, RDF_NIL, RDF_REST},
 	IndexedObject,
 };
 
+/// Remaining stack size below which a new stack segment is allocated before
+/// visiting the next list cell.
+const RED_ZONE: usize = 64 * 1024;
+
+/// Size of the stack segments allocated to visit long lists.
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
 impl<T, B, V: Vocabulary, I: Interpretation> LinkedDataResource<I, V> for List<T, B> {
 	fn interpretation(
 		&self,
@@ -41,12 +48,11 @@ where
 	B: LinkedDataResource<I, V> + LinkedDataSubject<I, V>,
 	V: IriVocabularyMut,
 {
-	fn visit_objects<S>(&self, mut visitor: S) -> Result<S::Ok, S::Error>
+	fn visit_objects<S>(&self, visitor: S) -> Result<S::Ok, S::Error>
 	where
 		S: linked_data_next::PredicateObjectsVisitor<I, V>,
 	{
-		visitor.object(self)?;
-		visitor.end()
+		Rest(self.as_slice()).visit_objects(visitor)
 	}
 }
 
@@ -80,6 +86,14 @@ where
 	}
 }
 
+/// Remaining items of a list, starting at a `rdf:first`/`rdf:rest` cell.
+///
+/// Each cell is an anonymous subject whose `rdf:rest` object is the next
+/// cell, or `rdf:nil` after the last item. Since visitors describe anonymous
+/// objects by visiting them as nested subjects, the visit depth grows with the
+/// length of the list. Each cell is visited on a stack segment allocated on
+/// demand, so that very long lists do not exhaust the stack of the visiting
+/// thread.
 struct Rest<'a, T, B>(&'a [IndexedObject<T, B>]);
 
 impl<T, B, V: Vocabulary, I: Interpretation> LinkedDataResource<I, V> for Rest<'_, T, B> {
@@ -102,12 +116,14 @@ where
 	where
 		S: linked_data_next::SubjectVisitor<I, V>,
 	{
-		if let Some((first, rest)) = self.0.split_first() {
-			visitor.predicate(RDF_FIRST, first.inner())?;
-			visitor.predicate(RDF_REST, &Rest(rest))?;
-		}
+		stacker::maybe_grow(RED_ZONE, STACK_SEGMENT_SIZE, || {
+			if let Some((first, rest)) = self.0.split_first() {
+				visitor.predicate(RDF_FIRST, first.inner())?;
+				visitor.predicate(RDF_REST, &Rest(rest))?;
+			}
 
-		visitor.end()
+			visitor.end()
+		})
 	}
 }
 
@@ -122,7 +138,121 @@ where
 	where
 		S: linked_data_next::PredicateObjectsVisitor<I, V>,
 	{
-		visitor.object(self)?;
+		if self.0.is_empty() {
+			visitor.object(&Nil)?;
+		} else {
+			visitor.object(self)?;
+		}
+
 		visitor.end()
 	}
 }
+
+/// The `rdf:nil` resource, ending lists.
+pub(super) struct Nil;
+
+impl<V: Vocabulary, I: Interpretation> LinkedDataResource<I, V> for Nil
+where
+	V: IriVocabularyMut,
+{
+	fn interpretation(
+		&self,
+		vocabulary: &mut V,
+		_interpretation: &mut I,
+	) -> ResourceInterpretation<I, V> {
+		ResourceInterpretation::Uninterpreted(Some(CowRdfTerm::Owned(Term::Id(Id::Iri(
+			vocabulary.insert(RDF_NIL),
+		)))))
+	}
+}
+
+impl<V: Vocabulary, I: Interpretation> LinkedDataSubject<I, V> for Nil {
+	fn visit_subject<S>(&self, visitor: S) -> Result<S::Ok, S::Error>
+	where
+		S: linked_data_next::SubjectVisitor<I, V>,
+	{
+		visitor.end()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::test_utils::document;
+	use linked_data_next::RdfQuad;
+	use rdf_types::{generator, Id, Term};
+	use std::collections::HashMap;
+
+	fn quads(json: &str) -> Vec<RdfQuad> {
+		linked_data_next::to_lexical_quads(generator::Blank::new(), &document(json)).unwrap()
+	}
+
+	fn list_json(len: usize) -> String {
+		let items: Vec<_> = (0..len)
+			.map(|i| format!(r#"{{ "@value": "{i}" }}"#))
+			.collect();
+
+		format!(
+			r#"[{{
+				"@id": "http://example.org/s",
+				"http://example.org/p": [{{ "@list": [{}] }}]
+			}}]"#,
+			items.join(",")
+		)
+	}
+
+	/// Follows the `rdf:first`/`rdf:rest` chain starting at the object of
+	/// `http://example.org/p`, up to `rdf:nil`.
+	fn items(quads: &[RdfQuad]) -> Vec<String> {
+		let objects: HashMap<_, _> = quads.iter().map(|q| ((&q.0, q.1.as_str()), &q.2)).collect();
+		let object = |subject: &Id, predicate: &str| objects.get(&(subject, predicate)).copied();
+		let nil: Id = Id::Iri(super::RDF_NIL.to_owned());
+
+		let mut result = Vec::new();
+		let mut current = match object(
+			&Id::Iri("http://example.org/s".parse().unwrap()),
+			"http://example.org/p",
+		) {
+			Some(Term::Id(id)) => id.clone(),
+			_ => panic!("missing list head"),
+		};
+
+		while current != nil {
+			match object(&current, super::RDF_FIRST.as_str()) {
+				Some(Term::Literal(l)) => result.push(l.value.to_string()),
+				_ => panic!("missing first"),
+			}
+
+			current = match object(&current, super::RDF_REST.as_str()) {
+				Some(Term::Id(id)) => id.clone(),
+				_ => panic!("missing rest"),
+			};
+		}
+
+		result
+	}
+
+	#[test]
+	fn cells() {
+		let quads = quads(&list_json(3));
+		assert_eq!(quads.len(), 7);
+		assert_eq!(items(&quads), ["0", "1", "2"])
+	}
+
+	#[test]
+	fn empty_list() {
+		let quads = quads(&list_json(0));
+		assert_eq!(quads.len(), 1);
+		assert_eq!(
+			quads[0].2.as_iri().map(|i| i.as_iri()),
+			Some(super::RDF_NIL)
+		);
+		assert!(items(&quads).is_empty())
+	}
+
+	#[test]
+	fn long_list() {
+		let quads = quads(&list_json(100_000));
+		assert_eq!(quads.len(), 200_001);
+		assert_eq!(items(&quads).len(), 100_000)
+	}
+}
@@ -4,7 +4,8 @@ use linked_data_next::{
 };
 use rdf_types::{vocabulary::IriVocabularyMut, Interpretation, Vocabulary};
 
-use crate::{rdf::RDF_TYPE, IndexedNode, IndexedObject, Node};
+use super::list::Nil;
+use crate::{rdf::RDF_TYPE, IndexedNode, IndexedObject, Node, Object};
 
 impl<T, B, V: Vocabulary, I: Interpretation> LinkedDataResource<I, V> for Node<T, B>
 where
@@ -163,7 +164,10 @@ where
 		S: linked_data_next::PredicateObjectsVisitor<I, V>,
 	{
 		for object in self.0 {
-			visitor.object(object.inner())?;
+			match object.inner() {
+				Object::List(list) if list.is_empty() => visitor.object(&Nil)?,
+				object => visitor.object(object)?,
+			}
 		}
 
 		visitor.end()
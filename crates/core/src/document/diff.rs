@@ -0,0 +1,520 @@
+//! Expanded documents comparison.
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use indexmap::{IndexMap, IndexSet};
+
+use super::ExpandedDocument;
+use crate::{
+	object::{
+		node::{
+			self,
+			multiset::{DeterministicHasher, DeterministicHasherBuilder},
+			Graph, Included, Properties, ReverseProperties,
+		},
+		FragmentRef, Literal,
+	},
+	Id, IndexedNode, IndexedObject, Node, Object, ValidId, Value,
+};
+
+/// Differences between two expanded documents.
+///
+/// Objects are compared structurally, with values compared using
+/// [`Object::is_equivalent`].
+///
+/// Blank nodes are matched by structure, not by label: each blank node of
+/// the old document is mapped to a blank node of the new document with the
+/// same structural signature, computed from the objects it occurs in and the
+/// blank nodes it is connected to. Two documents only differing by their
+/// blank node labels have no differences, while documents where blank nodes
+/// are connected differently do. Highly symmetric blank node structures
+/// may be mapped suboptimally, and then reported as different.
+///
+/// Objects are paired greedily, in document order, with the first equal
+/// counterpart left in the other document, which takes quadratic time in the
+/// number of top level objects.
+///
+/// See [`ExpandedDocument::diff`].
+#[derive(Debug)]
+pub struct DocumentDiff<'a, T, B> {
+	/// Top level objects of the new document without counterpart in the old
+	/// document.
+	pub added: Vec<&'a IndexedObject<T, B>>,
+
+	/// Top level objects of the old document without counterpart in the new
+	/// document.
+	pub removed: Vec<&'a IndexedObject<T, B>>,
+
+	/// Top level nodes found in both documents, identified by the same IRI,
+	/// that only differ by their property values.
+	pub changed: Vec<NodeDiff<'a, T, B>>,
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> DocumentDiff<'a, T, B> {
+	/// Computes the differences between the `old` and `new` documents.
+	pub fn new(old: &'a ExpandedDocument<T, B>, new: &'a ExpandedDocument<T, B>) -> Self {
+		let matcher = Matcher::new(old, new);
+		let (mut removed, mut added) =
+			unmatched(old, new, |a, b| matcher.same_indexed_object(a, b));
+
+		let mut changed = Vec::new();
+		let mut i = 0;
+		while i < removed.len() {
+			let object = removed[i];
+			let counterpart = object
+				.as_node()
+				.filter(|node| node.id.as_ref().is_some_and(Id::is_iri))
+				.and_then(|old_node| {
+					added.iter().position(|other| {
+						other.index() == object.index()
+							&& other.as_node().is_some_and(|new_node| {
+								old_node.id == new_node.id
+									&& matcher.same_entries(old_node, new_node)
+							})
+					})
+				});
+
+			match counterpart {
+				Some(j) => {
+					let new_node = added.remove(j).as_node().unwrap();
+					let old_node = removed.remove(i).as_node().unwrap();
+					changed.push(NodeDiff::new(&matcher, old_node, new_node))
+				}
+				None => i += 1,
+			}
+		}
+
+		Self {
+			added,
+			removed,
+			changed,
+		}
+	}
+}
+
+impl<T, B> DocumentDiff<'_, T, B> {
+	/// Checks if both documents are the same.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// Property value of a node: property and object.
+pub type PropertyValue<'a, T, B> = (&'a Id<T, B>, &'a IndexedObject<T, B>);
+
+/// Property values differences of a node found in both documents.
+#[derive(Debug)]
+pub struct NodeDiff<'a, T, B> {
+	/// Node identifier.
+	pub id: &'a Id<T, B>,
+
+	/// Property values only found in the new document.
+	pub added: Vec<PropertyValue<'a, T, B>>,
+
+	/// Property values only found in the old document.
+	pub removed: Vec<PropertyValue<'a, T, B>>,
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> NodeDiff<'a, T, B> {
+	fn new(matcher: &Matcher<B>, old: &'a Node<T, B>, new: &'a Node<T, B>) -> Self {
+		let (removed, added) = unmatched(bindings(old), bindings(new), |(pa, a), (pb, b)| {
+			matcher.same_id(Some(pa), Some(pb)) && matcher.same_indexed_object(a, b)
+		});
+
+		Self {
+			id: old.id.as_ref().unwrap(),
+			added,
+			removed,
+		}
+	}
+}
+
+fn bindings<T, B>(node: &Node<T, B>) -> impl Iterator<Item = PropertyValue<'_, T, B>> {
+	node.properties()
+		.iter()
+		.flat_map(|(prop, objects)| objects.iter().map(move |object| (prop, object)))
+}
+
+/// Matches the items of `a` and `b` one to one, and returns the items left
+/// unmatched on each side.
+fn unmatched<X>(
+	a: impl IntoIterator<Item = X>,
+	b: impl IntoIterator<Item = X>,
+	eq: impl Fn(&X, &X) -> bool,
+) -> (Vec<X>, Vec<X>) {
+	let mut b: Vec<_> = b.into_iter().collect();
+	let mut a_rest = Vec::new();
+
+	for x in a {
+		match b.iter().position(|y| eq(&x, y)) {
+			Some(j) => {
+				b.remove(j);
+			}
+			None => a_rest.push(x),
+		}
+	}
+
+	(a_rest, b)
+}
+
+fn same_set<X>(
+	a: impl IntoIterator<Item = X>,
+	b: impl IntoIterator<Item = X>,
+	eq: impl Fn(&X, &X) -> bool,
+) -> bool {
+	let (a, b) = unmatched(a, b, eq);
+	a.is_empty() && b.is_empty()
+}
+
+fn same_option<X>(a: Option<X>, b: Option<X>, eq: impl Fn(X, X) -> bool) -> bool {
+	match (a, b) {
+		(Some(a), Some(b)) => eq(a, b),
+		(None, None) => true,
+		_ => false,
+	}
+}
+
+/// Structural signatures of the blank node identifiers of a document.
+///
+/// Signatures are refined in rounds: the signature of a blank node
+/// identifier is combined with the hash of every top level object it occurs
+/// in, where the other blank node identifiers are replaced by their current
+/// signature. Blank nodes with different signatures are structurally
+/// different, while blank nodes with the same signature most likely are
+/// interchangeable.
+struct Signatures<'a, T, B> {
+	/// Top level objects, with the blank node identifiers they contain.
+	objects: Vec<(&'a IndexedObject<T, B>, IndexSet<&'a B>)>,
+
+	/// Current signature of each blank node identifier, in order of first
+	/// occurrence.
+	signatures: IndexMap<&'a B, u64>,
+
+	/// Number of distinct signatures.
+	classes: usize,
+}
+
+impl<'a, T: Hash, B: Eq + Hash> Signatures<'a, T, B> {
+	fn new(document: &'a ExpandedDocument<T, B>) -> Self {
+		let objects: Vec<_> = document
+			.iter()
+			.map(|object| (object, blank_ids(object)))
+			.collect();
+
+		let signatures: IndexMap<_, _> = objects
+			.iter()
+			.flat_map(|(_, blank_ids)| blank_ids.iter().map(|b| (*b, 0)))
+			.collect();
+
+		Self {
+			objects,
+			classes: usize::from(!signatures.is_empty()),
+			signatures,
+		}
+	}
+
+	/// Refines the signatures, and returns `true` if blank nodes that
+	/// previously shared the same signature have been told apart.
+	fn refine(&mut self) -> bool {
+		let mut contexts: HashMap<&B, u64> = HashMap::new();
+		for (object, blank_ids) in &self.objects {
+			for b in blank_ids {
+				let hash = Hashing {
+					signatures: &self.signatures,
+					focus: b,
+				}
+				.indexed_object(object);
+
+				let context = contexts.entry(*b).or_default();
+				*context = context.wrapping_add(hash)
+			}
+		}
+
+		for (b, signature) in &mut self.signatures {
+			*signature = hash_one(|h| {
+				signature.hash(h);
+				contexts.get(b).copied().unwrap_or_default().hash(h)
+			})
+		}
+
+		let classes = self.signatures.values().collect::<HashSet<_>>().len();
+		let refined = classes > self.classes;
+		self.classes = classes;
+		refined
+	}
+}
+
+/// Returns the blank node identifiers occurring in the given object.
+fn blank_ids<T, B: Eq + Hash>(object: &IndexedObject<T, B>) -> IndexSet<&B> {
+	object
+		.traverse()
+		.flat_map(|fragment| {
+			let (id, reverse_properties) = match fragment {
+				FragmentRef::NodeFragment(node::FragmentRef::Value(
+					node::EntryValueRef::Reverse(r),
+				)) => (None, Some(r)),
+				fragment => (fragment.into_id().and_then(Id::into_blank), None),
+			};
+
+			id.into_iter().chain(
+				reverse_properties
+					.into_iter()
+					.flat_map(|r| r.iter().filter_map(|(prop, _)| prop.as_blank())),
+			)
+		})
+		.collect()
+}
+
+fn hash_one(f: impl FnOnce(&mut DeterministicHasher)) -> u64 {
+	let mut hasher = DeterministicHasherBuilder.build_hasher();
+	f(&mut hasher);
+	hasher.finish()
+}
+
+/// Order independent hash of the given items.
+fn hash_unordered<X>(items: impl IntoIterator<Item = X>, f: impl Fn(X) -> u64) -> u64 {
+	items
+		.into_iter()
+		.fold(0u64, |sum, item| sum.wrapping_add(f(item)))
+}
+
+/// Structural hash of an object, seen from one of its blank nodes.
+struct Hashing<'s, 'a, B> {
+	signatures: &'s IndexMap<&'a B, u64>,
+
+	/// Blank node identifier the hash is computed for.
+	focus: &'a B,
+}
+
+impl<B: Eq + Hash> Hashing<'_, '_, B> {
+	fn id<T: Hash>(&self, id: &Id<T, B>) -> u64 {
+		hash_one(|h| match id {
+			Id::Valid(ValidId::Iri(iri)) => {
+				0u8.hash(h);
+				iri.hash(h)
+			}
+			Id::Valid(ValidId::Blank(b)) if b == self.focus => 1u8.hash(h),
+			Id::Valid(ValidId::Blank(b)) => {
+				2u8.hash(h);
+				self.signatures.get(b).hash(h)
+			}
+			Id::Invalid(id) => {
+				3u8.hash(h);
+				id.hash(h)
+			}
+		})
+	}
+
+	fn indexed_object<T: Hash>(&self, object: &IndexedObject<T, B>) -> u64 {
+		hash_one(|h| {
+			object.index().hash(h);
+			self.object(object).hash(h)
+		})
+	}
+
+	fn indexed_node<T: Hash>(&self, node: &IndexedNode<T, B>) -> u64 {
+		hash_one(|h| {
+			node.index().hash(h);
+			self.node(node).hash(h)
+		})
+	}
+
+	fn object<T: Hash>(&self, object: &Object<T, B>) -> u64 {
+		hash_one(|h| match object {
+			Object::Value(value) => {
+				0u8.hash(h);
+				hash_value(value, h)
+			}
+			Object::List(list) => {
+				1u8.hash(h);
+				list.len().hash(h);
+				for item in list.iter() {
+					self.indexed_object(item).hash(h)
+				}
+			}
+			Object::Node(node) => {
+				2u8.hash(h);
+				self.node(node).hash(h)
+			}
+		})
+	}
+
+	fn node<T: Hash>(&self, node: &Node<T, B>) -> u64 {
+		hash_one(|h| {
+			node.id.as_ref().map(|id| self.id(id)).hash(h);
+			hash_unordered(node.types(), |ty| self.id(ty)).hash(h);
+			node.graph_entry()
+				.map(|graph| hash_unordered(graph, |object| self.indexed_object(object)))
+				.hash(h);
+			node.included_entry()
+				.map(|included| hash_unordered(included, |node| self.indexed_node(node)))
+				.hash(h);
+			hash_unordered(node.properties(), |(prop, objects)| {
+				hash_one(|h| {
+					self.id(prop).hash(h);
+					hash_unordered(objects, |object| self.indexed_object(object)).hash(h)
+				})
+			})
+			.hash(h);
+			node.reverse_properties_entry()
+				.map(|reverse_properties| {
+					hash_unordered(reverse_properties, |(prop, nodes)| {
+						hash_one(|h| {
+							self.id(prop).hash(h);
+							hash_unordered(nodes, |node| self.indexed_node(node)).hash(h)
+						})
+					})
+				})
+				.hash(h)
+		})
+	}
+}
+
+/// Hashes a value consistently with [`Value::is_equivalent`].
+///
+/// JSON literals are only hashed by kind, since equivalent JSON objects may
+/// list their entries in any order.
+fn hash_value<T: Hash>(value: &Value<T>, h: &mut DeterministicHasher) {
+	match value {
+		Value::Literal(literal, ty) => {
+			0u8.hash(h);
+			ty.hash(h);
+			match literal {
+				Literal::Number(n) => n.canonical().hash(h),
+				literal => literal.hash(h),
+			}
+		}
+		Value::LangString(s) => {
+			1u8.hash(h);
+			s.as_str().hash(h);
+			s.language().hash(h);
+			s.direction().hash(h)
+		}
+		Value::Json(_) => 2u8.hash(h),
+	}
+}
+
+/// Structural comparison of objects from the old and new documents.
+///
+/// Blank node identifiers are compared through a one to one mapping from the
+/// blank nodes of the old document to the blank nodes of the new document
+/// that have the same [`Signatures`].
+struct Matcher<'a, B> {
+	blank_ids: HashMap<&'a B, &'a B>,
+}
+
+impl<'a, B: Eq + Hash> Matcher<'a, B> {
+	fn new<T: Hash>(old: &'a ExpandedDocument<T, B>, new: &'a ExpandedDocument<T, B>) -> Self {
+		let mut old = Signatures::new(old);
+		let mut new = Signatures::new(new);
+
+		// Both documents are refined the same number of times so that their
+		// signatures can be compared.
+		for _ in 0..=old.signatures.len().max(new.signatures.len()) {
+			let old_refined = old.refine();
+			let new_refined = new.refine();
+			if !old_refined && !new_refined {
+				break;
+			}
+		}
+
+		let mut candidates: HashMap<u64, Vec<&B>> = HashMap::new();
+		for (b, signature) in new.signatures.iter().rev() {
+			candidates.entry(*signature).or_default().push(b)
+		}
+
+		let blank_ids = old
+			.signatures
+			.iter()
+			.filter_map(|(a, signature)| Some((*a, candidates.get_mut(signature)?.pop()?)))
+			.collect();
+
+		Self { blank_ids }
+	}
+
+	fn same_id<T: PartialEq>(&self, a: Option<&Id<T, B>>, b: Option<&Id<T, B>>) -> bool {
+		match (a, b) {
+			(Some(Id::Valid(ValidId::Blank(a))), Some(Id::Valid(ValidId::Blank(b)))) => {
+				self.blank_ids.get(a).is_some_and(|a| *a == b)
+			}
+			(a, b) => a == b,
+		}
+	}
+
+	fn same_indexed_object<T: Eq + Hash>(
+		&self,
+		a: &IndexedObject<T, B>,
+		b: &IndexedObject<T, B>,
+	) -> bool {
+		a.index() == b.index() && self.same_object(a, b)
+	}
+
+	fn same_indexed_node<T: Eq + Hash>(
+		&self,
+		a: &IndexedNode<T, B>,
+		b: &IndexedNode<T, B>,
+	) -> bool {
+		a.index() == b.index() && self.same_node(a, b)
+	}
+
+	fn same_object<T: Eq + Hash>(&self, a: &Object<T, B>, b: &Object<T, B>) -> bool {
+		match (a, b) {
+			(Object::Value(a), Object::Value(b)) => a.is_equivalent(b),
+			(Object::List(a), Object::List(b)) => {
+				a.len() == b.len()
+					&& a.iter()
+						.zip(b.iter())
+						.all(|(a, b)| self.same_indexed_object(a, b))
+			}
+			(Object::Node(a), Object::Node(b)) => self.same_node(a, b),
+			_ => false,
+		}
+	}
+
+	fn same_node<T: Eq + Hash>(&self, a: &Node<T, B>, b: &Node<T, B>) -> bool {
+		self.same_id(a.id.as_ref(), b.id.as_ref())
+			&& self.same_entries(a, b)
+			&& self.same_properties(a.properties(), b.properties())
+	}
+
+	/// Compares the entries of the given nodes, except for their identifier
+	/// and properties.
+	fn same_entries<T: Eq + Hash>(&self, a: &Node<T, B>, b: &Node<T, B>) -> bool {
+		same_set(a.types(), b.types(), |a, b| self.same_id(Some(a), Some(b)))
+			&& same_option(a.graph_entry(), b.graph_entry(), |a, b| {
+				self.same_graph(a, b)
+			}) && same_option(a.included_entry(), b.included_entry(), |a, b| {
+			self.same_included(a, b)
+		}) && same_option(
+			a.reverse_properties_entry(),
+			b.reverse_properties_entry(),
+			|a, b| self.same_reverse_properties(a, b),
+		)
+	}
+
+	fn same_graph<T: Eq + Hash>(&self, a: &Graph<T, B>, b: &Graph<T, B>) -> bool {
+		same_set(a, b, |a, b| self.same_indexed_object(a, b))
+	}
+
+	fn same_included<T: Eq + Hash>(&self, a: &Included<T, B>, b: &Included<T, B>) -> bool {
+		same_set(a, b, |a, b| self.same_indexed_node(a, b))
+	}
+
+	fn same_properties<T: Eq + Hash>(&self, a: &Properties<T, B>, b: &Properties<T, B>) -> bool {
+		same_set(a, b, |(pa, a), (pb, b)| {
+			self.same_id(Some(*pa), Some(*pb))
+				&& same_set(*a, *b, |a, b| self.same_indexed_object(a, b))
+		})
+	}
+
+	fn same_reverse_properties<T: Eq + Hash>(
+		&self,
+		a: &ReverseProperties<T, B>,
+		b: &ReverseProperties<T, B>,
+	) -> bool {
+		same_set(a, b, |(pa, a), (pb, b)| {
+			self.same_id(Some(*pa), Some(*pb))
+				&& same_set(*a, *b, |a, b| self.same_indexed_node(a, b))
+		})
+	}
+}
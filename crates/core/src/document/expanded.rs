@@ -1,23 +1,75 @@
-use crate::object::{FragmentRef, InvalidExpandedJson, Traverse};
+use crate::object::{graph, node, FragmentRef, InvalidExpandedJson, Traverse};
+use crate::DocumentDiff;
 use crate::{Id, Indexed, IndexedObject, Node, Object, Relabel, TryFromJson};
 use hashbrown::HashMap;
 use indexmap::IndexSet;
 use iref::IriBuf;
-use rdf_types::vocabulary::VocabularyMut;
+use rdf_types::vocabulary::{
+	BlankIdVocabulary, BlankIdVocabularyMut, EmbedIntoVocabulary, ExtractFromVocabulary,
+	IriVocabulary, IriVocabularyMut, VocabularyMut,
+};
 use rdf_types::{BlankIdBuf, Generator, Vocabulary};
+use std::cell::RefCell;
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::sync::OnceLock;
 
 /// Result of the document expansion algorithm.
 ///
 /// It is just an alias for a set of (indexed) objects.
-#[derive(Debug, Clone)]
-pub struct ExpandedDocument<T = IriBuf, B = BlankIdBuf>(IndexSet<IndexedObject<T, B>>);
+#[derive(Clone)]
+pub struct ExpandedDocument<T = IriBuf, B = BlankIdBuf>(
+	IndexSet<IndexedObject<T, B>>,
+	NodeIndex<T, B>,
+);
 
 impl<T, B> Default for ExpandedDocument<T, B> {
 	#[inline(always)]
 	fn default() -> Self {
-		Self(IndexSet::new())
+		Self(IndexSet::new(), NodeIndex::default())
+	}
+}
+
+impl<T: std::fmt::Debug, B: std::fmt::Debug> std::fmt::Debug for ExpandedDocument<T, B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_tuple("ExpandedDocument").field(&self.0).finish()
+	}
+}
+
+/// Index of the top level node objects of a document, by identifier.
+///
+/// Built on demand by [`ExpandedDocument::node_by_id`], and cleared whenever
+/// the document is mutated.
+#[derive(Clone)]
+struct NodeIndex<T, B>(OnceLock<HashMap<Id<T, B>, usize>>);
+
+impl<T, B> Default for NodeIndex<T, B> {
+	fn default() -> Self {
+		Self(OnceLock::new())
+	}
+}
+
+impl<T, B> NodeIndex<T, B> {
+	fn clear(&mut self) {
+		self.0.take();
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> NodeIndex<T, B> {
+	/// Returns the index, building it from the given objects if necessary.
+	///
+	/// If several nodes share the same identifier, the first one is indexed.
+	fn get_or_build(&self, objects: &IndexSet<IndexedObject<T, B>>) -> &HashMap<Id<T, B>, usize> {
+		self.0.get_or_init(|| {
+			let mut index = HashMap::new();
+			for (i, object) in objects.iter().enumerate() {
+				if let Some(id) = object.as_node().and_then(|node| node.id.as_ref()) {
+					index.entry(id.clone()).or_insert(i);
+				}
+			}
+
+			index
+		})
 	}
 }
 
@@ -42,6 +94,12 @@ impl<T, B> ExpandedDocument<T, B> {
 		&self.0
 	}
 
+	/// Returns a mutable reference to the objects, clearing the node index.
+	fn objects_mut(&mut self) -> &mut IndexSet<IndexedObject<T, B>> {
+		self.1.clear();
+		&mut self.0
+	}
+
 	#[inline(always)]
 	pub fn into_objects(self) -> IndexSet<IndexedObject<T, B>> {
 		self.0
@@ -73,7 +131,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Eq + Hash,
 		B: Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = std::mem::take(self.objects_mut());
 		for mut object in objects {
 			object.identify_all_with(vocabulary, generator);
 			self.0.insert(object);
@@ -104,7 +162,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Clone + Eq + Hash,
 		B: Clone + Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = std::mem::take(self.objects_mut());
 		let mut relabeling = HashMap::new();
 		let mut buffer = ryu_js::Buffer::new();
 		for mut object in objects {
@@ -137,7 +195,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Clone + Eq + Hash,
 		B: Clone + Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = std::mem::take(self.objects_mut());
 		let mut relabeling = HashMap::new();
 		for mut object in objects {
 			object.relabel_with(vocabulary, generator, &mut relabeling);
@@ -165,7 +223,7 @@ impl<T, B> ExpandedDocument<T, B> {
 		T: Eq + Hash,
 		B: Eq + Hash,
 	{
-		let objects = std::mem::take(&mut self.0);
+		let objects = std::mem::take(self.objects_mut());
 		for mut object in objects {
 			object.canonicalize_with(buffer);
 			self.0.insert(object);
@@ -182,6 +240,18 @@ impl<T, B> ExpandedDocument<T, B> {
 		self.canonicalize_with(&mut buffer)
 	}
 
+	/// Recursively sorts this document objects, giving it a deterministic
+	/// order.
+	///
+	/// See [`Object::stable_cmp`] for the ordering used.
+	pub fn sort(&mut self)
+	where
+		T: Ord + Eq + Hash,
+		B: Ord + Eq + Hash,
+	{
+		crate::object::sort_set(self.objects_mut(), Object::sort, Object::stable_cmp)
+	}
+
 	/// Map the identifiers present in this expanded document (recursively).
 	pub fn map_ids<U, C>(
 		self,
@@ -197,6 +267,7 @@ impl<T, B> ExpandedDocument<T, B> {
 				.into_iter()
 				.map(|i| i.map_inner(|o| o.map_ids(&mut map_iri, &mut map_id)))
 				.collect(),
+			NodeIndex::default(),
 		)
 	}
 
@@ -210,6 +281,30 @@ impl<T, B> ExpandedDocument<T, B> {
 			.collect()
 	}
 
+	/// Returns an iterator over all the IRIs referenced in the document.
+	///
+	/// This includes node identifiers, types, properties and reverse
+	/// properties, and value datatypes, including those of nested graphs,
+	/// included nodes and lists. An IRI is yielded once per occurrence.
+	pub fn iris(&self) -> impl '_ + Iterator<Item = &T> {
+		self.traverse().flat_map(|fragment| {
+			let (iri, reverse_properties) = match fragment {
+				FragmentRef::NodeFragment(node::FragmentRef::Value(
+					node::EntryValueRef::Reverse(r),
+				)) => (None, Some(r)),
+				FragmentRef::NodeFragment(f) => (f.into_id().and_then(Id::as_iri), None),
+				FragmentRef::ValueFragment(f) => (f.into_iri(), None),
+				_ => (None, None),
+			};
+
+			iri.into_iter().chain(
+				reverse_properties
+					.into_iter()
+					.flat_map(|r| r.iter().filter_map(|(prop, _)| prop.as_iri())),
+			)
+		})
+	}
+
 	/// Returns the main node object of the document, if any.
 	///
 	/// The main node is the unique top level (root) node object. If multiple
@@ -254,7 +349,119 @@ impl<T, B> ExpandedDocument<T, B> {
 impl<T: Hash + Eq, B: Hash + Eq> ExpandedDocument<T, B> {
 	#[inline(always)]
 	pub fn insert(&mut self, object: IndexedObject<T, B>) -> bool {
-		self.0.insert(object)
+		self.objects_mut().insert(object)
+	}
+
+	/// Inserts the given object, unless an equivalent object is already
+	/// present.
+	///
+	/// Objects are compared with [`Object::is_equivalent`], and must have the
+	/// same index. Returns `false`, and leaves the document unchanged, if an
+	/// equivalent object was found.
+	pub fn try_insert(&mut self, object: IndexedObject<T, B>) -> bool {
+		let duplicate = self
+			.0
+			.iter()
+			.any(|o| o.index() == object.index() && o.is_equivalent(&object));
+
+		!duplicate && self.objects_mut().insert(object)
+	}
+
+	/// Merges the objects of `other` into this document.
+	///
+	/// Node objects sharing the same valid identifier are merged into a single
+	/// node: their types are unioned, their property values are appended and
+	/// the members of their `@graph` are merged the same way. Nodes without a
+	/// valid identifier and value objects are appended as-is.
+	pub fn merge(&mut self, other: Self)
+	where
+		T: Clone,
+		B: Clone,
+	{
+		let objects = std::mem::take(self.objects_mut());
+		self.0 = graph::merge_objects(objects, other.0)
+	}
+
+	/// Keeps only the content of the named graph `name`.
+	///
+	/// The document is replaced by the members of the `@graph` entry of the
+	/// top level node objects identified by `name`. Every other top level
+	/// object is dropped. The document is left empty if there is no such
+	/// graph.
+	pub fn retain_graph(&mut self, name: &Id<T, B>) {
+		for object in std::mem::take(self.objects_mut()) {
+			if let Object::Node(node) = object.into_inner() {
+				if node.id.as_ref() == Some(name) {
+					self.0.extend(node.graph.into_iter().flatten())
+				}
+			}
+		}
+	}
+
+	/// Keeps only the content of the default graph.
+	///
+	/// The `@graph` entry of every top level node object is removed. Nodes
+	/// that are left empty, because they only named a graph, are dropped.
+	pub fn retain_default_graph(&mut self) {
+		for mut object in std::mem::take(self.objects_mut()) {
+			if let Object::Node(node) = object.inner_mut() {
+				if node.graph.take().is_some() && node.is_empty() {
+					continue;
+				}
+			}
+
+			self.0.insert(object);
+		}
+	}
+
+	/// Returns the top level node object identified by `id`, if any.
+	///
+	/// The first lookup indexes the top level nodes by identifier, so that
+	/// the following ones take constant time until the document is mutated.
+	/// If several nodes share the same identifier, the first one is returned.
+	pub fn node_by_id(&self, id: &Id<T, B>) -> Option<&Node<T, B>>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		let i = *self.1.get_or_build(&self.0).get(id)?;
+		self.0.get_index(i)?.as_node()
+	}
+
+	/// Computes the differences between this document and the `other`
+	/// (newer) document.
+	///
+	/// See [`DocumentDiff`] for how objects are compared. Blank nodes are
+	/// matched by structure, and objects are paired in quadratic time.
+	pub fn diff<'a>(&'a self, other: &'a Self) -> DocumentDiff<'a, T, B> {
+		DocumentDiff::new(self, other)
+	}
+}
+
+impl<T: Eq + Hash> ExpandedDocument<T, BlankIdBuf> {
+	/// Replaces every blank node identifier with a fresh label.
+	///
+	/// Labels `_:b0`, `_:b1`, etc. are issued in document order, the same
+	/// label replacing every occurrence of a given identifier. Documents that
+	/// only differ by their blank node labels hence become equal. Unlike
+	/// [`Self::relabel`], anonymous nodes are left anonymous.
+	pub fn strip_blank_nodes(&mut self) {
+		let mut labels: HashMap<BlankIdBuf, BlankIdBuf> = HashMap::new();
+		*self = std::mem::take(self).map_ids(
+			|iri| iri,
+			|id| {
+				id.map(|id| match id {
+					rdf_types::Id::Blank(b) => {
+						let n = labels.len();
+						let label = labels
+							.entry(b)
+							.or_insert_with(|| BlankIdBuf::new(format!("_:b{n}")).unwrap());
+						rdf_types::Id::Blank(label.clone())
+					}
+					iri => iri,
+				})
+			},
+		)
 	}
 }
 
@@ -268,6 +475,63 @@ impl<T: Eq + Hash, B: Eq + Hash> From<Indexed<Node<T, B>>> for ExpandedDocument<
 	}
 }
 
+/// Interns the identifiers of the document into the vocabulary.
+///
+/// With an [`IndexVocabulary`](rdf_types::vocabulary::IndexVocabulary), each
+/// distinct IRI or blank node identifier is stored once and replaced by its
+/// index in the document.
+impl<V: IriVocabularyMut + BlankIdVocabularyMut> EmbedIntoVocabulary<V> for ExpandedDocument
+where
+	V::Iri: Eq + Hash,
+	V::BlankId: Eq + Hash,
+{
+	type Embedded = ExpandedDocument<V::Iri, V::BlankId>;
+
+	fn embed_into_vocabulary(self, vocabulary: &mut V) -> Self::Embedded {
+		let vocabulary = RefCell::new(vocabulary);
+		self.map_ids(
+			|iri| vocabulary.borrow_mut().insert_owned(iri),
+			|id| {
+				id.map(|id| match id {
+					rdf_types::Id::Iri(iri) => {
+						rdf_types::Id::Iri(vocabulary.borrow_mut().insert_owned(iri))
+					}
+					rdf_types::Id::Blank(b) => {
+						rdf_types::Id::Blank(vocabulary.borrow_mut().insert_owned_blank_id(b))
+					}
+				})
+			},
+		)
+	}
+}
+
+/// Resolves the interned identifiers of the document.
+///
+/// # Panics
+///
+/// Panics if an identifier is unknown to the vocabulary.
+impl<V: IriVocabulary + BlankIdVocabulary> ExtractFromVocabulary<V>
+	for ExpandedDocument<V::Iri, V::BlankId>
+{
+	type Extracted = ExpandedDocument;
+
+	fn extract_from_vocabulary(self, vocabulary: &V) -> Self::Extracted {
+		self.map_ids(
+			|iri| vocabulary.owned_iri(iri).ok().unwrap(),
+			|id| {
+				id.map(|id| match id {
+					rdf_types::Id::Iri(iri) => {
+						rdf_types::Id::Iri(vocabulary.owned_iri(iri).ok().unwrap())
+					}
+					rdf_types::Id::Blank(b) => {
+						rdf_types::Id::Blank(vocabulary.owned_blank_id(b).ok().unwrap())
+					}
+				})
+			},
+		)
+	}
+}
+
 impl<T: Eq + Hash, B: Eq + Hash> TryFromJson<T, B> for ExpandedDocument<T, B> {
 	fn try_from_json_in(
 		vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>,
@@ -331,18 +595,508 @@ impl<T, B> Iterator for IntoIter<T, B> {
 
 impl<T: Hash + Eq, B: Hash + Eq> FromIterator<IndexedObject<T, B>> for ExpandedDocument<T, B> {
 	fn from_iter<I: IntoIterator<Item = IndexedObject<T, B>>>(iter: I) -> Self {
-		Self(iter.into_iter().collect())
+		Self(iter.into_iter().collect(), NodeIndex::default())
 	}
 }
 
 impl<T: Hash + Eq, B: Hash + Eq> Extend<IndexedObject<T, B>> for ExpandedDocument<T, B> {
 	fn extend<I: IntoIterator<Item = IndexedObject<T, B>>>(&mut self, iter: I) {
-		self.0.extend(iter)
+		self.objects_mut().extend(iter)
 	}
 }
 
 impl<T, B> From<IndexSet<IndexedObject<T, B>>> for ExpandedDocument<T, B> {
 	fn from(set: IndexSet<IndexedObject<T, B>>) -> Self {
-		Self(set)
+		Self(set, NodeIndex::default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::document::diff::PropertyValue;
+	use crate::test_utils::document;
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	#[test]
+	fn merge_halves() {
+		let mut a = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"@type": ["http://example.org/Person"],
+				"http://example.org/name": [{ "@value": "Alice" }],
+				"@graph": [{
+					"@id": "http://example.org/g",
+					"http://example.org/p": [{ "@value": 1 }]
+				}]
+			}]"#,
+		);
+
+		let b = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"@type": ["http://example.org/Person", "http://example.org/Agent"],
+				"http://example.org/age": [{ "@value": 42 }],
+				"@graph": [{
+					"@id": "http://example.org/g",
+					"http://example.org/q": [{ "@value": 2 }]
+				}]
+			}]"#,
+		);
+
+		a.merge(b);
+
+		let expected = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"@type": ["http://example.org/Person", "http://example.org/Agent"],
+				"http://example.org/name": [{ "@value": "Alice" }],
+				"http://example.org/age": [{ "@value": 42 }],
+				"@graph": [{
+					"@id": "http://example.org/g",
+					"http://example.org/p": [{ "@value": 1 }],
+					"http://example.org/q": [{ "@value": 2 }]
+				}]
+			}]"#,
+		);
+
+		assert_eq!(a, expected)
+	}
+
+	#[test]
+	fn values_are_kept() {
+		let mut a = document(
+			r#"[
+				{ "@value": "x" },
+				{ "http://example.org/p": [{ "@value": "x" }] },
+				{ "@id": "http://example.org/a", "http://example.org/p": [{ "@value": "x" }] }
+			]"#,
+		);
+
+		let b = document(
+			r#"[
+				{ "@value": "x", "@language": "en" },
+				{ "http://example.org/p": [{ "@value": "y" }] },
+				{ "@id": "http://example.org/a", "http://example.org/p": [{ "@value": "x", "@language": "en" }] }
+			]"#,
+		);
+
+		a.merge(b);
+		assert_eq!(a.len(), 5);
+		assert_eq!(a.iter().filter(|o| o.is_value()).count(), 2);
+
+		let node = a
+			.iter()
+			.filter_map(|o| o.as_node())
+			.find(|n| n.id.is_some())
+			.unwrap();
+		let p = Id::iri(IriBuf::new("http://example.org/p".to_owned()).unwrap());
+		assert_eq!(node.get(&p).count(), 2)
+	}
+
+	#[test]
+	fn iris() {
+		let document = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"@type": ["http://example.org/Person"],
+				"http://example.org/birthDate": [{
+					"@value": "1990-01-01",
+					"@type": "http://www.w3.org/2001/XMLSchema#date"
+				}],
+				"http://example.org/knows": [{
+					"@list": [{ "@id": "http://example.org/b" }]
+				}],
+				"@reverse": {
+					"http://example.org/parent": [{ "@id": "http://example.org/c" }]
+				}
+			}]"#,
+		);
+
+		let mut iris: Vec<_> = document.iris().map(IriBuf::as_str).collect();
+		iris.sort_unstable();
+		assert_eq!(
+			iris,
+			[
+				"http://example.org/Person",
+				"http://example.org/a",
+				"http://example.org/b",
+				"http://example.org/birthDate",
+				"http://example.org/c",
+				"http://example.org/knows",
+				"http://example.org/parent",
+				"http://www.w3.org/2001/XMLSchema#date"
+			]
+		)
+	}
+
+	#[test]
+	fn sort() {
+		let mut scrambled = document(
+			r#"[
+				{ "@id": "_:x", "http://example.org/p": [{ "@value": "b" }] },
+				{
+					"@id": "http://example.org/b",
+					"http://example.org/q": [
+						{ "@id": "_:y" },
+						{ "@value": "z" },
+						{ "@id": "http://example.org/c" },
+						{ "@value": "a" }
+					],
+					"http://example.org/p": [
+						{ "@value": "1", "@type": "http://example.org/T" },
+						{ "@value": "1" }
+					]
+				},
+				{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/Z", "_:t", "http://example.org/A"]
+				}
+			]"#,
+		);
+
+		let expected = document(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/A", "http://example.org/Z", "_:t"]
+				},
+				{
+					"@id": "http://example.org/b",
+					"http://example.org/p": [
+						{ "@value": "1" },
+						{ "@value": "1", "@type": "http://example.org/T" }
+					],
+					"http://example.org/q": [
+						{ "@value": "a" },
+						{ "@value": "z" },
+						{ "@id": "http://example.org/c" },
+						{ "@id": "_:y" }
+					]
+				},
+				{ "@id": "_:x", "http://example.org/p": [{ "@value": "b" }] }
+			]"#,
+		);
+
+		scrambled.sort();
+		assert_eq!(scrambled, expected);
+
+		// Equality ignores the order, so compare it explicitly.
+		for (sorted, expected) in scrambled.iter().zip(expected.iter()) {
+			let (sorted, expected) = (sorted.as_node().unwrap(), expected.as_node().unwrap());
+			assert_eq!(sorted.id, expected.id);
+			assert_eq!(sorted.types(), expected.types());
+
+			let properties = sorted.properties().iter().zip(expected.properties());
+			for ((sorted_prop, sorted), (expected_prop, expected)) in properties {
+				assert_eq!(sorted_prop, expected_prop);
+				assert_eq!(sorted, expected)
+			}
+		}
+	}
+
+	#[test]
+	fn try_insert() {
+		let object = |json: &str| {
+			let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+			IndexedObject::try_from_json_in(no_vocabulary_mut(), json).unwrap()
+		};
+
+		let mut document = ExpandedDocument::new();
+		assert!(document.try_insert(object(r#"{ "@value": "x" }"#)));
+		assert!(!document.try_insert(object(r#"{ "@value": "x" }"#)));
+		assert!(document.try_insert(object(r#"{ "@value": 1 }"#)));
+		assert!(!document.try_insert(object(r#"{ "@value": 1.0 }"#)));
+		assert!(document.try_insert(object(r#"{ "@value": "x", "@index": "i" }"#)));
+		assert_eq!(document.len(), 3)
+	}
+
+	#[test]
+	fn retain_graph() {
+		let dataset = r#"[
+			{ "@id": "http://example.org/a", "http://example.org/p": [{ "@value": "default" }] },
+			{
+				"@id": "http://example.org/g1",
+				"@graph": [
+					{ "@id": "http://example.org/b", "http://example.org/p": [{ "@value": "g1" }] },
+					{ "@id": "http://example.org/c" }
+				]
+			},
+			{
+				"@id": "http://example.org/g2",
+				"http://example.org/p": [{ "@value": "g2 metadata" }],
+				"@graph": [
+					{ "@id": "http://example.org/d", "http://example.org/p": [{ "@value": "g2" }] }
+				]
+			}
+		]"#;
+
+		let mut g1 = document(dataset);
+		g1.retain_graph(&Id::iri(
+			IriBuf::new("http://example.org/g1".to_owned()).unwrap(),
+		));
+		assert_eq!(
+			g1,
+			document(
+				r#"[
+					{ "@id": "http://example.org/b", "http://example.org/p": [{ "@value": "g1" }] },
+					{ "@id": "http://example.org/c" }
+				]"#
+			)
+		);
+
+		let mut default_graph = document(dataset);
+		default_graph.retain_default_graph();
+		assert_eq!(
+			default_graph,
+			document(
+				r#"[
+					{ "@id": "http://example.org/a", "http://example.org/p": [{ "@value": "default" }] },
+					{ "@id": "http://example.org/g2", "http://example.org/p": [{ "@value": "g2 metadata" }] }
+				]"#
+			)
+		)
+	}
+
+	#[test]
+	fn map_ids() {
+		let document = document(
+			r#"[{
+				"@id": "http://old/a",
+				"@type": ["http://old/Person"],
+				"http://old/name": [{ "@value": "Alice" }],
+				"http://old/age": [{ "@value": "42", "@type": "http://old/int" }],
+				"http://old/knows": [{ "@id": "_:b" }],
+				"@reverse": {
+					"http://old/parent": [{ "@id": "http://old/c" }]
+				},
+				"@graph": [{
+					"@id": "_:b",
+					"http://old/p": [{ "@list": [{ "@id": "http://old/d" }] }]
+				}]
+			}]"#,
+		);
+
+		let rewrite = |iri: IriBuf| match iri.as_str().strip_prefix("http://old/") {
+			Some(suffix) => IriBuf::new(format!("http://new/{suffix}")).unwrap(),
+			None => iri,
+		};
+
+		let mapped = document.map_ids(rewrite, |id| {
+			id.map(|id| match id {
+				rdf_types::Id::Iri(iri) => rdf_types::Id::Iri(rewrite(iri)),
+				rdf_types::Id::Blank(b) => rdf_types::Id::Blank(b),
+			})
+		});
+
+		assert_eq!(mapped.iris().count(), 10);
+		assert!(mapped
+			.iris()
+			.all(|iri| iri.as_str().starts_with("http://new/")));
+
+		let blank_ids: Vec<_> = mapped.blank_ids().into_iter().collect();
+		assert_eq!(blank_ids, [&BlankIdBuf::new("_:b".to_owned()).unwrap()])
+	}
+
+	#[test]
+	fn interning() {
+		let document = document(
+			r#"[
+				{ "@id": "http://example.org/a", "http://example.org/name": [{ "@value": "Alice" }] },
+				{ "@id": "_:b", "http://example.org/name": [{ "@value": "Bob" }] }
+			]"#,
+		);
+
+		let mut vocabulary: rdf_types::vocabulary::IndexVocabulary =
+			rdf_types::vocabulary::IndexVocabulary::new();
+		let interned = document.clone().embed_into_vocabulary(&mut vocabulary);
+
+		let predicates: Vec<_> = interned
+			.iter()
+			.flat_map(|object| object.as_node().unwrap().properties())
+			.map(|(prop, _)| prop.as_iri().copied().unwrap())
+			.collect();
+		assert_eq!(predicates.len(), 2);
+		assert_eq!(predicates[0], predicates[1]);
+		assert_eq!(
+			vocabulary.iri(&predicates[0]).map(iref::Iri::as_str),
+			Some("http://example.org/name")
+		);
+
+		assert_eq!(interned.extract_from_vocabulary(&vocabulary), document)
+	}
+
+	#[test]
+	fn strip_blank_nodes() {
+		let labeled = |a: &str, b: &str| {
+			document(&format!(
+				r#"[
+					{{
+						"@id": "_:{a}",
+						"http://example.org/knows": [{{ "@id": "_:{b}" }}],
+						"http://example.org/name": [{{ "@value": "Alice" }}]
+					}},
+					{{
+						"@id": "_:{b}",
+						"http://example.org/knows": [{{ "@id": "_:{a}" }}, {{ "@id": "http://example.org/c" }}]
+					}},
+					{{ "http://example.org/name": [{{ "@value": "anonymous" }}] }}
+				]"#
+			))
+		};
+
+		let mut a = labeled("alice", "bob");
+		let mut b = labeled("x", "y");
+		assert_ne!(a, b);
+
+		a.strip_blank_nodes();
+		b.strip_blank_nodes();
+		assert_eq!(a, b);
+		assert_eq!(a, labeled("b0", "b1"));
+		assert_eq!(a.iter().filter(|o| o.id().is_none()).count(), 1)
+	}
+
+	#[test]
+	fn diff() {
+		let old = document(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"http://example.org/name": [{ "@value": "Alice" }],
+					"http://example.org/age": [{ "@value": 41 }]
+				},
+				{
+					"@id": "_:b0",
+					"http://example.org/name": [{ "@value": "Bob" }]
+				},
+				{
+					"@id": "http://example.org/c",
+					"http://example.org/name": [{ "@value": "Carol" }]
+				}
+			]"#,
+		);
+
+		let new = document(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"http://example.org/name": [{ "@value": "Alice" }],
+					"http://example.org/age": [{ "@value": 42 }]
+				},
+				{
+					"@id": "_:other",
+					"http://example.org/name": [{ "@value": "Bob" }]
+				},
+				{
+					"@id": "http://example.org/c",
+					"http://example.org/name": [{ "@value": "Carol" }]
+				}
+			]"#,
+		);
+
+		assert!(old.diff(&old).is_empty());
+
+		let diff = old.diff(&new);
+		assert!(diff.added.is_empty());
+		assert!(diff.removed.is_empty());
+		assert_eq!(diff.changed.len(), 1);
+
+		let node = &diff.changed[0];
+		assert_eq!(node.id.as_str(), "http://example.org/a");
+
+		let age = |diff: &[PropertyValue<IriBuf, BlankIdBuf>]| {
+			assert_eq!(diff.len(), 1);
+			assert_eq!(diff[0].0.as_str(), "http://example.org/age");
+			diff[0]
+				.1
+				.as_value()
+				.unwrap()
+				.as_number()
+				.unwrap()
+				.to_string()
+		};
+		assert_eq!(age(&node.removed), "41");
+		assert_eq!(age(&node.added), "42")
+	}
+
+	#[test]
+	fn diff_blank_nodes_by_structure() {
+		let labeled = |known: &str, b: &str, c: &str| {
+			document(&format!(
+				r#"[
+					{{
+						"@id": "_:a",
+						"http://example.org/knows": [{{ "@id": "_:{known}" }}]
+					}},
+					{{
+						"@id": "_:{b}",
+						"http://example.org/name": [{{ "@value": "Bob" }}]
+					}},
+					{{
+						"@id": "_:{c}",
+						"http://example.org/name": [{{ "@value": "Carol" }}]
+					}}
+				]"#
+			))
+		};
+
+		let old = labeled("b", "b", "c");
+		assert!(old.diff(&labeled("c", "c", "b")).is_empty());
+
+		let new = labeled("c", "b", "c");
+		let diff = old.diff(&new);
+		assert_eq!(diff.removed.len(), 3);
+		assert_eq!(diff.added.len(), 3);
+		assert!(diff.changed.is_empty())
+	}
+
+	#[test]
+	fn diff_shared_blank_node() {
+		let old = document(
+			r#"[
+				{ "@id": "http://example.org/s", "http://example.org/p": [{ "@id": "_:x" }] },
+				{ "@id": "http://example.org/t", "http://example.org/p": [{ "@id": "_:y" }] }
+			]"#,
+		);
+
+		let new = document(
+			r#"[
+				{ "@id": "http://example.org/s", "http://example.org/p": [{ "@id": "_:x" }] },
+				{ "@id": "http://example.org/t", "http://example.org/p": [{ "@id": "_:x" }] }
+			]"#,
+		);
+
+		let diff = old.diff(&new);
+		assert!(!diff.is_empty());
+		assert_eq!(diff.changed.len(), 2)
+	}
+
+	#[test]
+	fn node_by_id() {
+		let mut doc = document(
+			r#"[
+				{ "@value": "not a node" },
+				{ "@id": "http://example.org/a", "http://example.org/name": [{ "@value": "Alice" }] }
+			]"#,
+		);
+
+		let a = Id::iri(IriBuf::new("http://example.org/a".to_owned()).unwrap());
+		let b = Id::iri(IriBuf::new("http://example.org/b".to_owned()).unwrap());
+
+		assert_eq!(
+			doc.node_by_id(&a).and_then(|node| node.id.as_ref()),
+			Some(&a)
+		);
+		assert!(doc.node_by_id(&b).is_none());
+
+		doc.insert(Indexed::new(Object::node(Node::with_id(b.clone())), None));
+		assert_eq!(
+			doc.node_by_id(&b).and_then(|node| node.id.as_ref()),
+			Some(&b)
+		);
+
+		doc.retain_graph(&a);
+		assert!(doc.node_by_id(&a).is_none())
 	}
 }
@@ -5,11 +5,15 @@ use iref::IriBuf;
 use linked_data_next::{LinkedData, LinkedDataGraph, LinkedDataResource, LinkedDataSubject};
 use rdf_types::{vocabulary::IriVocabularyMut, BlankIdBuf, Interpretation, Vocabulary};
 
+pub mod diff;
 pub mod expanded;
 pub mod flattened;
+pub mod validation;
 
+pub use diff::{DocumentDiff, NodeDiff};
 pub use expanded::ExpandedDocument;
 pub use flattened::FlattenedDocument;
+pub use validation::ValidationError;
 
 use crate::RemoteDocument;
 
@@ -0,0 +1,196 @@
+//! Expanded documents structural validation.
+use super::ExpandedDocument;
+use crate::{Id, IndexedObject, Node, Object, ProcessingMode};
+
+/// Structural well-formedness violation of an expanded document.
+///
+/// Some invariants are enforced by the object model itself and need no
+/// validation: a value object cannot have both a language and a datatype,
+/// and datatypes are IRIs.
+///
+/// See [`ExpandedDocument::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+	/// Node identifier that is neither an absolute IRI nor a blank node
+	/// identifier.
+	#[error("invalid node identifier `{0}`")]
+	InvalidNodeId(String),
+
+	/// Node type that is neither an absolute IRI nor a blank node identifier.
+	#[error("invalid node type `{0}`")]
+	InvalidNodeType(String),
+
+	/// Property that is neither an absolute IRI nor a blank node identifier.
+	#[error("invalid property `{0}`")]
+	InvalidProperty(String),
+
+	/// List directly containing another list, which is only allowed since
+	/// JSON-LD 1.1.
+	#[error("list of lists")]
+	ListOfLists,
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Checks that this document is well-formed, according to JSON-LD 1.1.
+	///
+	/// Returns every violation found, including those of nested graphs,
+	/// included nodes and lists.
+	pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+		self.validate_with(ProcessingMode::JsonLd1_1)
+	}
+
+	/// Checks that this document is well-formed, according to the given
+	/// processing mode.
+	///
+	/// See [`Self::validate`].
+	pub fn validate_with(&self, mode: ProcessingMode) -> Result<(), Vec<ValidationError>> {
+		let mut errors = Vec::new();
+		for object in self {
+			validate_object(object, mode, &mut errors)
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+}
+
+fn validate_object<T, B>(
+	object: &IndexedObject<T, B>,
+	mode: ProcessingMode,
+	errors: &mut Vec<ValidationError>,
+) {
+	match object.inner() {
+		Object::Value(_) => (),
+		Object::List(list) => {
+			for item in list.iter() {
+				if mode == ProcessingMode::JsonLd1_0 && item.is_list() {
+					errors.push(ValidationError::ListOfLists)
+				}
+
+				validate_object(item, mode, errors)
+			}
+		}
+		Object::Node(node) => validate_node(node, mode, errors),
+	}
+}
+
+fn validate_node<T, B>(node: &Node<T, B>, mode: ProcessingMode, errors: &mut Vec<ValidationError>) {
+	if let Some(Id::Invalid(id)) = &node.id {
+		errors.push(ValidationError::InvalidNodeId(id.clone()))
+	}
+
+	for ty in node.types() {
+		if let Id::Invalid(ty) = ty {
+			errors.push(ValidationError::InvalidNodeType(ty.clone()))
+		}
+	}
+
+	for (prop, objects) in node.properties() {
+		validate_property(prop, errors);
+		for object in objects {
+			validate_object(object, mode, errors)
+		}
+	}
+
+	if let Some(reverse_properties) = &node.reverse_properties {
+		for (prop, nodes) in reverse_properties {
+			validate_property(prop, errors);
+			for node in nodes {
+				validate_node(node, mode, errors)
+			}
+		}
+	}
+
+	if let Some(included) = &node.included {
+		for node in included {
+			validate_node(node, mode, errors)
+		}
+	}
+
+	if let Some(graph) = &node.graph {
+		for object in graph {
+			validate_object(object, mode, errors)
+		}
+	}
+}
+
+fn validate_property<T, B>(prop: &Id<T, B>, errors: &mut Vec<ValidationError>) {
+	if let Id::Invalid(prop) = prop {
+		errors.push(ValidationError::InvalidProperty(prop.clone()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::document;
+	use crate::TryFromJson;
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	#[test]
+	fn well_formed() {
+		let document = document(
+			r#"[{
+				"@id": "_:a",
+				"@type": ["http://example.org/Person"],
+				"http://example.org/name": [{ "@value": "Alice", "@language": "en" }],
+				"http://example.org/list": [{ "@list": [{ "@list": [] }] }]
+			}]"#,
+		);
+
+		assert_eq!(document.validate(), Ok(()))
+	}
+
+	#[test]
+	fn invalid_ids() {
+		let document = document(
+			r#"[{
+				"@id": "relative",
+				"@type": ["Person"],
+				"@graph": [{
+					"@id": "http://example.org/a",
+					"name": [{ "@value": "Alice" }]
+				}]
+			}]"#,
+		);
+
+		assert_eq!(
+			document.validate(),
+			Err(vec![
+				ValidationError::InvalidNodeId("relative".to_owned()),
+				ValidationError::InvalidNodeType("Person".to_owned()),
+				ValidationError::InvalidProperty("name".to_owned())
+			])
+		)
+	}
+
+	#[test]
+	fn list_of_lists() {
+		let document = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/list": [{ "@list": [{ "@list": [{ "@value": 1 }] }] }]
+			}]"#,
+		);
+
+		assert_eq!(document.validate(), Ok(()));
+		assert_eq!(
+			document.validate_with(ProcessingMode::JsonLd1_0),
+			Err(vec![ValidationError::ListOfLists])
+		)
+	}
+
+	#[test]
+	fn language_and_type() {
+		// Such value objects cannot be represented in the first place.
+		let (json, _) = json_syntax::Value::parse_str(
+			r#"[{ "@value": "x", "@language": "en", "@type": "http://example.org/t" }]"#,
+		)
+		.unwrap();
+		assert!(ExpandedDocument::try_from_json_in(no_vocabulary_mut(), json).is_err())
+	}
+}
@@ -2,7 +2,7 @@
 use crate::flattened::UnorderedFlattenedDocument;
 use crate::{ExpandedDocument, FlattenedDocument, IndexedNode, IndexedObject, Object};
 use contextual::WithContext;
-use rdf_types::{Generator, Vocabulary};
+use rdf_types::{vocabulary::BlankIdVocabularyMut, Generator, Vocabulary};
 use std::collections::HashSet;
 use std::hash::Hash;
 
@@ -85,6 +85,37 @@ impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for ExpandedDocum
 	}
 }
 
+impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<I, B> {
+	/// Flattens this document, deterministically relabeling blank nodes.
+	///
+	/// Every node without identifier and every blank node identifier is
+	/// assigned a fresh `_:b0`, `_:b1`, ... label, in document order. The
+	/// same blank node identifier always receives the same label, and the
+	/// labels only depend on the input document.
+	pub fn flatten_deterministic(self, ordered: bool) -> FlattenResult<I, B>
+	where
+		(): Vocabulary<Iri = I, BlankId = B> + BlankIdVocabularyMut,
+	{
+		self.flatten_deterministic_with(rdf_types::vocabulary::no_vocabulary_mut(), ordered)
+	}
+
+	/// Flattens this document using the given vocabulary, deterministically
+	/// relabeling blank nodes.
+	///
+	/// See [`Self::flatten_deterministic`].
+	pub fn flatten_deterministic_with<V>(
+		self,
+		vocabulary: &mut V,
+		ordered: bool,
+	) -> FlattenResult<I, B>
+	where
+		V: Vocabulary<Iri = I, BlankId = B> + BlankIdVocabularyMut,
+	{
+		let generator = rdf_types::generator::Blank::new_with_prefix("b".to_owned());
+		self.flatten_with(vocabulary, generator, ordered)
+	}
+}
+
 fn filter_graph<T, B>(node: IndexedNode<T, B>) -> Option<IndexedNode<T, B>> {
 	if node.index().is_none() && node.is_empty() {
 		None
@@ -178,3 +209,78 @@ impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> NodeMap<T, B> {
 			.collect()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::document;
+
+	const INPUT: &str = r#"[
+		{
+			"@id": "http://example.org/a",
+			"http://example.org/p": [{
+				"@id": "_:shared",
+				"http://example.org/q": [{ "@value": "v" }]
+			}]
+		},
+		{
+			"@id": "http://example.org/b",
+			"http://example.org/p": [{ "@id": "_:shared" }],
+			"http://example.org/r": [{
+				"http://example.org/q": [{ "@value": "w" }]
+			}]
+		}
+	]"#;
+
+	fn flatten(json: &str) -> FlattenedDocument<iref::IriBuf, rdf_types::BlankIdBuf> {
+		let mut nodes = document(json).flatten_deterministic(false).unwrap();
+		nodes.sort_by(|a, b| {
+			a.id.as_ref()
+				.unwrap()
+				.as_str()
+				.cmp(b.id.as_ref().unwrap().as_str())
+		});
+		nodes
+	}
+
+	fn reference(node: &IndexedNode<iref::IriBuf, rdf_types::BlankIdBuf>, prop: &str) -> String {
+		let prop = crate::Id::iri(iref::IriBuf::new(prop.to_owned()).unwrap());
+		node.get(&prop)
+			.next()
+			.unwrap()
+			.as_node()
+			.unwrap()
+			.id
+			.as_ref()
+			.unwrap()
+			.as_str()
+			.to_owned()
+	}
+
+	#[test]
+	fn stable_labels() {
+		assert_eq!(flatten(INPUT), flatten(INPUT))
+	}
+
+	#[test]
+	fn shared_blank_node() {
+		let nodes = flatten(INPUT);
+		let ids: Vec<_> = nodes
+			.iter()
+			.map(|n| n.id.as_ref().unwrap().as_str())
+			.collect();
+		assert_eq!(
+			ids,
+			[
+				"_:b0",
+				"_:b1",
+				"http://example.org/a",
+				"http://example.org/b"
+			]
+		);
+
+		assert_eq!(reference(&nodes[2], "http://example.org/p"), "_:b0");
+		assert_eq!(reference(&nodes[3], "http://example.org/p"), "_:b0");
+		assert_eq!(reference(&nodes[3], "http://example.org/r"), "_:b1")
+	}
+}
@@ -0,0 +1,744 @@
+//! Framing algorithm.
+//!
+//! See <https://www.w3.org/TR/json-ld11-framing/>.
+use std::collections::HashSet;
+
+use iref::IriBuf;
+use json_syntax::Value as Json;
+use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+
+use crate::{
+	flattening::{ConflictingIndexes, NodeMapGraph},
+	object::value::Literal,
+	ExpandedDocument, Id, Indexed, IndexedNode, Node, Object, TryFromJson, Value,
+};
+
+/// Expanded object.
+type IndexedObject = crate::IndexedObject<IriBuf, BlankIdBuf>;
+
+/// Node embedding policy.
+///
+/// This is the `@embed` frame flag.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Embed {
+	/// Always embed matched nodes, unless it would create a cycle.
+	Always,
+
+	/// Embed a node the first time it is matched, then only output node
+	/// references.
+	#[default]
+	Once,
+
+	/// Never embed matched nodes, always output node references.
+	Never,
+}
+
+/// Framing options.
+///
+/// Gives the default value of the frame flags, which can be overridden by
+/// each frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+	/// Default `@embed` flag.
+	pub embed: Embed,
+
+	/// Default `@explicit` flag: only output the properties listed in the
+	/// frame.
+	pub explicit: bool,
+
+	/// Default `@requireAll` flag: a node must match every property of the
+	/// frame, instead of any of them.
+	pub require_all: bool,
+
+	/// Default `@omitDefault` flag: do not add default values for the frame
+	/// properties missing from a matched node.
+	pub omit_default: bool,
+}
+
+/// Framing error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("invalid frame")]
+	InvalidFrame,
+
+	#[error(transparent)]
+	ConflictingIndexes(#[from] ConflictingIndexes<IriBuf, BlankIdBuf>),
+}
+
+/// Frames the given expanded document.
+///
+/// The `frame` must be in expanded form and can be either a frame object or
+/// an array containing a single frame object. Frames with duplicate entries
+/// are rejected. Nodes of every graph of the
+/// document are merged before framing, and blank nodes are relabeled. Each
+/// top-level node matching the frame is returned, with the nodes it
+/// references embedded according to the frame.
+pub fn frame(
+	document: &ExpandedDocument,
+	frame: &Json,
+	options: Options,
+) -> Result<ExpandedDocument, Error> {
+	let frame = frame_object(frame)
+		.filter(|_| !has_duplicate_entries(frame))
+		.ok_or(Error::InvalidFrame)?;
+
+	let node_map = document
+		.generate_node_map_with(
+			no_vocabulary_mut(),
+			rdf_types::generator::Blank::new_with_prefix("b".to_owned()),
+		)?
+		.merge();
+
+	let mut framer = Framer {
+		nodes: &node_map,
+		options,
+		embedded: HashSet::new(),
+		stack: Vec::new(),
+	};
+
+	let ids: Vec<_> = node_map.nodes().filter_map(|n| n.id.clone()).collect();
+	let result = framer.match_frame(ids, Some(frame), Flags::from_options(options), true);
+
+	Ok(result.into_iter().collect())
+}
+
+/// Returns the frame object of the given frame value, if any.
+fn frame_object(frame: &Json) -> Option<&json_syntax::Object> {
+	match frame {
+		Json::Object(object) => Some(object),
+		Json::Array(array) => match array.as_slice() {
+			[] => None,
+			[Json::Object(object)] => Some(object),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+#[derive(Clone, Copy)]
+struct Flags {
+	embed: Embed,
+	explicit: bool,
+	require_all: bool,
+	omit_default: bool,
+}
+
+impl Flags {
+	fn from_options(options: Options) -> Self {
+		Self {
+			embed: options.embed,
+			explicit: options.explicit,
+			require_all: options.require_all,
+			omit_default: options.omit_default,
+		}
+	}
+
+	/// Reads the flags of the given frame, falling back to the framing
+	/// options.
+	fn new(frame: Option<&json_syntax::Object>, options: Options) -> Self {
+		let mut flags = Self::from_options(options);
+
+		if let Some(frame) = frame {
+			if let Some(embed) = entry(frame, "@embed") {
+				flags.embed = match embed {
+					Json::String(s) if s.as_str() == "@always" => Embed::Always,
+					Json::String(s) if s.as_str() == "@never" => Embed::Never,
+					Json::Boolean(false) => Embed::Never,
+					_ => Embed::Once,
+				}
+			}
+
+			flags.explicit = boolean_flag(frame, "@explicit").unwrap_or(flags.explicit);
+			flags.require_all = boolean_flag(frame, "@requireAll").unwrap_or(flags.require_all);
+			flags.omit_default = boolean_flag(frame, "@omitDefault").unwrap_or(flags.omit_default);
+		}
+
+		flags
+	}
+}
+
+/// Returns the value of the given frame entry, unwrapping single element
+/// arrays.
+fn entry<'a>(frame: &'a json_syntax::Object, key: &str) -> Option<&'a Json> {
+	match frame.get_unique(key).ok().flatten()? {
+		Json::Array(array) if array.len() == 1 => Some(&array[0]),
+		value => Some(value),
+	}
+}
+
+fn boolean_flag(frame: &json_syntax::Object, key: &str) -> Option<bool> {
+	match entry(frame, key)? {
+		Json::Boolean(b) => Some(*b),
+		Json::Object(o) => match o.get_unique("@value").ok().flatten()? {
+			Json::Boolean(b) => Some(*b),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Checks if an object of the given frame has several entries with the same
+/// key.
+fn has_duplicate_entries(value: &Json) -> bool {
+	match value {
+		Json::Object(object) => object.iter().any(|entry| {
+			object.get_unique(entry.key.as_str()).is_err() || has_duplicate_entries(&entry.value)
+		}),
+		Json::Array(array) => array.iter().any(has_duplicate_entries),
+		_ => false,
+	}
+}
+
+/// Returns the values of the given frame entry, if any.
+fn values<'a>(frame: &'a json_syntax::Object, key: &str) -> Option<&'a [Json]> {
+	match frame.get_unique(key).ok().flatten()? {
+		Json::Array(array) => Some(array),
+		value => Some(std::slice::from_ref(value)),
+	}
+}
+
+fn is_wildcard(value: &Json) -> bool {
+	value.as_object().is_some_and(json_syntax::Object::is_empty)
+}
+
+fn is_keyword(key: &str) -> bool {
+	key.starts_with('@')
+}
+
+struct Framer<'a> {
+	nodes: &'a NodeMapGraph<IriBuf, BlankIdBuf>,
+	options: Options,
+
+	/// Nodes embedded in the current top-level output node.
+	embedded: HashSet<Id>,
+
+	/// Nodes being embedded, used to detect cycles.
+	stack: Vec<Id>,
+}
+
+impl Framer<'_> {
+	fn match_frame(
+		&mut self,
+		ids: Vec<Id>,
+		frame: Option<&json_syntax::Object>,
+		inherited: Flags,
+		top_level: bool,
+	) -> Vec<IndexedObject> {
+		let flags = match frame {
+			Some(frame) => Flags::new(Some(frame), self.options),
+			None => inherited,
+		};
+
+		let mut matches: Vec<_> = ids
+			.into_iter()
+			.filter_map(|id| self.nodes.get(&id))
+			.filter(|node| match frame {
+				Some(frame) => self.filter_node(node, frame, flags),
+				None => true,
+			})
+			.collect();
+		matches.sort_by(|a, b| id_str(a).cmp(id_str(b)));
+
+		let mut result = Vec::new();
+		for node in matches {
+			let id = node.id.clone().unwrap();
+
+			if top_level {
+				self.embedded.clear()
+			}
+
+			if flags.embed == Embed::Never
+				|| self.stack.contains(&id)
+				|| (flags.embed == Embed::Once && self.embedded.contains(&id))
+			{
+				result.push(Indexed::new(Object::node(Node::with_id(id)), None));
+				continue;
+			}
+
+			self.embedded.insert(id.clone());
+			self.stack.push(id.clone());
+			let output = self.embed(node, frame, flags);
+			self.stack.pop();
+
+			result.push(Indexed::new(
+				Object::node(output),
+				node.index().map(ToOwned::to_owned),
+			))
+		}
+
+		result
+	}
+
+	/// Builds the output node for the given matched `node`.
+	fn embed(
+		&mut self,
+		node: &IndexedNode,
+		frame: Option<&json_syntax::Object>,
+		flags: Flags,
+	) -> Node {
+		let mut output = Node::with_id(node.id.clone().unwrap());
+		output.types.clone_from(&node.types);
+
+		let mut properties: Vec<_> = node.properties().iter().collect();
+		properties.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+
+		for (prop, objects) in properties {
+			let prop_frame = frame.and_then(|frame| values(frame, prop.as_str()));
+			if flags.explicit && prop_frame.is_none() {
+				continue;
+			}
+
+			let subframe = prop_frame
+				.and_then(<[Json]>::first)
+				.and_then(Json::as_object);
+
+			for object in objects {
+				match object.inner() {
+					Object::Node(reference) => {
+						let ids = reference.id.iter().cloned().collect();
+						for embedded in self.match_frame(ids, subframe, flags, false) {
+							output.insert(prop.clone(), embedded)
+						}
+					}
+					Object::List(list) => {
+						let mut items = Vec::with_capacity(list.len());
+						for item in list {
+							match item.inner() {
+								Object::Node(reference) => {
+									let ids = reference.id.iter().cloned().collect();
+									items.extend(self.match_frame(ids, subframe, flags, false))
+								}
+								_ => items.push(item.clone()),
+							}
+						}
+
+						output.insert(
+							prop.clone(),
+							Indexed::new(
+								Object::List(crate::object::List::new(items)),
+								object.index().map(ToOwned::to_owned),
+							),
+						)
+					}
+					Object::Value(value) => {
+						let matches = match subframe {
+							Some(pattern) => value_match(pattern, value),
+							None => true,
+						};
+
+						if matches {
+							output.insert(prop.clone(), object.clone())
+						}
+					}
+				}
+			}
+		}
+
+		if let Some(frame) = frame {
+			self.insert_defaults(&mut output, frame, flags)
+		}
+
+		output
+	}
+
+	/// Adds the default value of every frame property missing from `output`,
+	/// unless `@omitDefault` is set.
+	fn insert_defaults(&self, output: &mut Node, frame: &json_syntax::Object, flags: Flags) {
+		let mut keys: Vec<_> = frame
+			.iter()
+			.map(|e| e.key.as_str())
+			.filter(|key| !is_keyword(key))
+			.collect();
+		keys.sort_unstable();
+
+		for key in keys {
+			let next = values(frame, key)
+				.and_then(<[Json]>::first)
+				.and_then(Json::as_object);
+
+			let omit_default = next
+				.and_then(|next| boolean_flag(next, "@omitDefault"))
+				.unwrap_or(flags.omit_default);
+
+			if omit_default || output.properties().iter().any(|(p, _)| p.as_str() == key) {
+				continue;
+			}
+
+			let defaults: Vec<_> = match next.and_then(|next| values(next, "@default")) {
+				Some(defaults) => defaults.iter().filter_map(default_object).collect(),
+				None => vec![Indexed::new(Object::Value(Value::null()), None)],
+			};
+
+			let prop = Id::from_string(key.to_owned());
+			for object in defaults {
+				output.insert(prop.clone(), object)
+			}
+		}
+	}
+
+	/// Checks if the given node matches `frame`.
+	fn filter_node(&self, node: &IndexedNode, frame: &json_syntax::Object, flags: Flags) -> bool {
+		if let Some(ids) = values(frame, "@id") {
+			let matches = ids
+				.iter()
+				.any(|id| is_wildcard(id) || id.as_str().is_some_and(|id| id == id_str(node)));
+
+			if !flags.require_all || !matches {
+				return matches;
+			}
+		}
+
+		let mut wildcard = true;
+		let mut matches_some = false;
+
+		if let Some(types) = values(frame, "@type") {
+			wildcard = false;
+
+			let matches = match types {
+				[] => node.types().is_empty(),
+				[ty] if is_wildcard(ty) => !node.types().is_empty(),
+				types => types.iter().any(|ty| match ty {
+					Json::Object(o) => o.get_unique("@default").ok().flatten().is_some(),
+					ty => ty
+						.as_str()
+						.is_some_and(|ty| node.types().iter().any(|t| t.as_str() == ty)),
+				}),
+			};
+
+			if !flags.require_all || !matches {
+				return matches;
+			}
+
+			matches_some = true
+		}
+
+		for entry in frame.iter() {
+			let key = entry.key.as_str();
+			if is_keyword(key) {
+				continue;
+			}
+
+			wildcard = false;
+
+			let Some(prop_frame) = values(frame, key) else {
+				continue;
+			};
+			let objects: Vec<_> = node
+				.properties()
+				.iter()
+				.filter(|(p, _)| p.as_str() == key)
+				.flat_map(|(_, objects)| objects)
+				.collect();
+
+			let subframe = prop_frame.first().and_then(Json::as_object);
+			let has_default =
+				subframe.is_some_and(|f| f.get_unique("@default").ok().flatten().is_some());
+
+			if objects.is_empty() && has_default {
+				continue;
+			}
+
+			if prop_frame.is_empty() && !objects.is_empty() {
+				return false;
+			}
+
+			let matches = match subframe {
+				None => prop_frame.is_empty(),
+				Some(pattern) if is_value_pattern(pattern) => {
+					objects.iter().any(|object| match object.inner() {
+						Object::Value(value) => value_match(pattern, value),
+						_ => false,
+					})
+				}
+				Some(pattern) if pattern.get_unique("@id").ok().flatten().is_some() => {
+					objects.iter().any(|object| match object.inner() {
+						Object::Node(reference) => reference
+							.id
+							.as_ref()
+							.and_then(|id| self.nodes.get(id))
+							.is_some_and(|node| self.filter_node(node, pattern, flags)),
+						_ => false,
+					})
+				}
+				Some(_) => !objects.is_empty(),
+			};
+
+			if !matches && flags.require_all {
+				return false;
+			}
+
+			matches_some |= matches
+		}
+
+		wildcard || matches_some
+	}
+}
+
+fn id_str(node: &IndexedNode) -> &str {
+	node.id.as_ref().map(Id::as_str).unwrap_or_default()
+}
+
+fn is_value_pattern(pattern: &json_syntax::Object) -> bool {
+	["@value", "@language"]
+		.iter()
+		.any(|key| pattern.get_unique(*key).ok().flatten().is_some())
+}
+
+/// Checks if the given value object matches the value `pattern`.
+///
+/// Each of the `@value`, `@type` and `@language` entries of the pattern
+/// either lists the accepted values, is a wildcard `{}` accepting any value,
+/// or is an empty array `[]` accepting only values without this entry.
+/// Missing entries accept anything.
+fn value_match(pattern: &json_syntax::Object, value: &Value) -> bool {
+	fn check(pattern: Option<&[Json]>, value: Option<&Json>) -> bool {
+		match pattern {
+			None => true,
+			Some([]) => value.is_none(),
+			Some([p]) if is_wildcard(p) => value.is_some(),
+			Some(patterns) => value.is_some_and(|v| patterns.contains(v)),
+		}
+	}
+
+	let (v, ty, language) = match value {
+		Value::Literal(lit, ty) => (
+			literal_json(lit),
+			ty.as_ref().map(|ty| Json::String(ty.as_str().into())),
+			None,
+		),
+		Value::LangString(s) => (
+			Json::String(s.as_str().into()),
+			None,
+			s.language()
+				.map(|l| Json::String(l.as_str().to_lowercase().into())),
+		),
+		Value::Json(json) => (
+			json.clone(),
+			Some(Json::String(crate::rdf::RDF_JSON.as_str().into())),
+			None,
+		),
+	};
+
+	let languages: Option<Vec<_>> = values(pattern, "@language").map(|l| {
+		l.iter()
+			.map(|l| match l {
+				Json::String(s) => Json::String(s.to_lowercase().into()),
+				other => other.clone(),
+			})
+			.collect()
+	});
+
+	check(values(pattern, "@value"), Some(&v))
+		&& check(values(pattern, "@type"), ty.as_ref())
+		&& check(languages.as_deref(), language.as_ref())
+}
+
+fn literal_json(literal: &Literal) -> Json {
+	match literal {
+		Literal::Null => Json::Null,
+		Literal::Boolean(b) => Json::Boolean(*b),
+		Literal::Number(n) => Json::Number(n.clone()),
+		Literal::String(s) => Json::String(s.as_str().into()),
+	}
+}
+
+/// Converts a frame `@default` value into an object.
+fn default_object(value: &Json) -> Option<IndexedObject> {
+	match value {
+		Json::Object(_) => IndexedObject::try_from_json_in(no_vocabulary_mut(), value.clone()).ok(),
+		Json::String(s) if s.as_str() == "@null" => {
+			Some(Indexed::new(Object::Value(Value::null()), None))
+		}
+		Json::Null => Some(Indexed::new(Object::Value(Value::null()), None)),
+		Json::Boolean(b) => Some(Indexed::new(
+			Object::Value(Value::Literal(Literal::Boolean(*b), None)),
+			None,
+		)),
+		Json::Number(n) => Some(Indexed::new(
+			Object::Value(Value::Literal(Literal::Number(n.clone()), None)),
+			None,
+		)),
+		Json::String(s) => Some(Indexed::new(
+			Object::Value(Value::Literal(Literal::String(s.as_str().into()), None)),
+			None,
+		)),
+		Json::Array(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_syntax::Parse;
+
+	fn json(s: &str) -> Json {
+		Json::parse_str(s).unwrap().0
+	}
+
+	fn document() -> ExpandedDocument {
+		let value = json(
+			r#"[
+				{
+					"@id": "http://example.org/library",
+					"@type": ["http://example.org/Library"],
+					"http://example.org/contains": [{ "@id": "http://example.org/book" }],
+					"http://example.org/featured": [{ "@id": "http://example.org/book" }]
+				},
+				{
+					"@id": "http://example.org/book",
+					"@type": ["http://example.org/Book"],
+					"http://example.org/title": [{ "@value": "The Title" }]
+				}
+			]"#,
+		);
+
+		ExpandedDocument::try_from_json_in(no_vocabulary_mut(), value).unwrap()
+	}
+
+	fn prop(node: &Node, p: &str) -> Vec<IndexedObject> {
+		let p = Id::iri(IriBuf::new(p.to_owned()).unwrap());
+		node.get(&p).cloned().collect()
+	}
+
+	fn single_node(document: &ExpandedDocument) -> &Node {
+		assert_eq!(document.len(), 1);
+		document.iter().next().unwrap().as_node().unwrap()
+	}
+
+	fn is_reference(object: &IndexedObject) -> bool {
+		object
+			.as_node()
+			.is_some_and(|n| n.types.is_none() && n.properties().is_empty())
+	}
+
+	#[test]
+	fn type_constrained() {
+		let frame = json(
+			r#"{
+				"@type": ["http://example.org/Library"],
+				"http://example.org/contains": [{ "@type": ["http://example.org/Book"] }]
+			}"#,
+		);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let library = single_node(&framed);
+		assert_eq!(
+			library.id.as_ref().unwrap().as_str(),
+			"http://example.org/library"
+		);
+
+		let contains = prop(library, "http://example.org/contains");
+		assert_eq!(contains.len(), 1);
+		let book = contains[0].as_node().unwrap();
+		assert_eq!(
+			book.id.as_ref().unwrap().as_str(),
+			"http://example.org/book"
+		);
+		assert_eq!(prop(book, "http://example.org/title").len(), 1);
+
+		// `@once`: the book was already embedded under `contains`.
+		let featured = prop(library, "http://example.org/featured");
+		assert!(is_reference(&featured[0]))
+	}
+
+	#[test]
+	fn duplicate_frame_entries() {
+		let frame = json(
+			r#"{
+				"@type": ["http://example.org/Library"],
+				"http://example.org/contains": [{}],
+				"http://example.org/contains": []
+			}"#,
+		);
+
+		assert!(matches!(
+			super::frame(&document(), &frame, Options::default()),
+			Err(Error::InvalidFrame)
+		))
+	}
+
+	#[test]
+	fn embed_always() {
+		let frame = json(r#"{ "@type": ["http://example.org/Library"], "@embed": "@always" }"#);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let library = single_node(&framed);
+		for p in ["http://example.org/contains", "http://example.org/featured"] {
+			assert!(!is_reference(&prop(library, p)[0]))
+		}
+	}
+
+	#[test]
+	fn embed_never() {
+		let frame = json(
+			r#"{
+				"@type": ["http://example.org/Library"],
+				"http://example.org/contains": [{ "@embed": "@never" }]
+			}"#,
+		);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let library = single_node(&framed);
+		assert!(is_reference(
+			&prop(library, "http://example.org/contains")[0]
+		));
+		assert!(!is_reference(
+			&prop(library, "http://example.org/featured")[0]
+		))
+	}
+
+	#[test]
+	fn id_pattern() {
+		let frame = json(r#"{ "@id": "http://example.org/book" }"#);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let book = single_node(&framed);
+		assert_eq!(
+			book.id.as_ref().unwrap().as_str(),
+			"http://example.org/book"
+		)
+	}
+
+	#[test]
+	fn value_pattern() {
+		let frame = json(
+			r#"{ "http://example.org/title": [{ "@value": "The Title" }], "@explicit": true }"#,
+		);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let book = single_node(&framed);
+		assert_eq!(
+			book.id.as_ref().unwrap().as_str(),
+			"http://example.org/book"
+		);
+		assert!(book.types.is_some());
+		assert_eq!(book.properties().len(), 1)
+	}
+
+	#[test]
+	fn default_values() {
+		let frame = json(
+			r#"{
+				"@type": ["http://example.org/Book"],
+				"http://example.org/author": [{ "@default": "Anonymous" }],
+				"http://example.org/isbn": [{}]
+			}"#,
+		);
+
+		let framed = super::frame(&document(), &frame, Options::default()).unwrap();
+		let book = single_node(&framed);
+		let author = prop(book, "http://example.org/author");
+		assert_eq!(author[0].as_value().unwrap().as_str(), Some("Anonymous"));
+		let isbn = prop(book, "http://example.org/isbn");
+		assert_eq!(isbn[0].as_value(), Some(&Value::null()));
+
+		let options = Options {
+			omit_default: true,
+			..Options::default()
+		};
+		let framed = super::frame(&document(), &frame, options).unwrap();
+		let book = single_node(&framed);
+		assert!(prop(book, "http://example.org/author").is_empty());
+		assert!(prop(book, "http://example.org/isbn").is_empty())
+	}
+}
@@ -68,7 +68,7 @@ impl<T> Indexed<T> {
 		(self.value, self.index)
 	}
 
-	/// Cast the inner value.
+	/// Maps the inner value, preserving the index.
 	#[inline(always)]
 	pub fn map_inner<U, F>(self, f: F) -> Indexed<U>
 	where
@@ -196,3 +196,24 @@ impl<T: IntoJsonWithContext<N>, N> IntoJsonWithContext<N> for Indexed<T> {
 // 		json
 // 	}
 // }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Id, Node, Object};
+	use iref::IriBuf;
+	use rdf_types::BlankIdBuf;
+
+	#[test]
+	fn map_inner() {
+		let id: Id = Id::iri(IriBuf::new("http://example.org/a".to_owned()).unwrap());
+		let object: Indexed<Object<IriBuf, BlankIdBuf>> = Indexed::new(
+			Object::node(Node::with_id(id.clone())),
+			Some("a".to_owned()),
+		);
+
+		let mapped = object.map_inner(|object| object.into_node().unwrap().id);
+		assert_eq!(mapped.index(), Some("a"));
+		assert_eq!(mapped.into_inner(), Some(id))
+	}
+}
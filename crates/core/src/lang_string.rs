@@ -75,6 +75,14 @@ impl LangString {
 			.map(|tag| tag.as_lenient_lang_tag_ref())
 	}
 
+	/// Checks if the associated language tag matches the given `tag`.
+	///
+	/// Language tags are compared ASCII case-insensitively, as is
+	/// [`LenientLangTag`] equality. The stored form is left untouched.
+	pub fn matches_language(&self, tag: &str) -> bool {
+		self.language() == Some(LenientLangTag::new(tag).0)
+	}
+
 	/// Sets the associated language tag.
 	///
 	/// If `None` is given, the direction must be set,
@@ -128,6 +136,23 @@ impl LangString {
 		}
 	}
 
+	/// Partitions the given language strings by direction.
+	///
+	/// Strings without direction are grouped separately. The relative order of
+	/// the strings is preserved within each group.
+	pub fn split_by_direction(strings: impl IntoIterator<Item = Self>) -> ByDirection {
+		let mut result = ByDirection::default();
+		for s in strings {
+			match s.direction {
+				Some(Direction::Ltr) => result.ltr.push(s),
+				Some(Direction::Rtl) => result.rtl.push(s),
+				None => result.none.push(s),
+			}
+		}
+
+		result
+	}
+
 	/// Returns a reference to this lang string as a [`LangStr`].
 	pub fn as_lang_str(&self) -> LangStr {
 		LangStr {
@@ -188,6 +213,21 @@ impl LangString {
 	}
 }
 
+/// Language strings partitioned by direction.
+///
+/// See [`LangString::split_by_direction`].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct ByDirection {
+	/// Left-to-right strings.
+	pub ltr: Vec<LangString>,
+
+	/// Right-to-left strings.
+	pub rtl: Vec<LangString>,
+
+	/// Strings without direction.
+	pub none: Vec<LangString>,
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for LangString {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -275,3 +315,45 @@ impl<'a> LangStr<'a> {
 		self.direction
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn lang_string(language: &str) -> LangString {
+		LangString::new(
+			"color".into(),
+			Some(LenientLangTagBuf::new(language.to_owned()).0),
+			None,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn case_insensitive_language() {
+		let a = lang_string("en-US");
+		assert!(a.matches_language("EN-us"));
+		assert!(!a.matches_language("en-GB"));
+		assert_eq!(a, lang_string("EN-us"));
+		assert_eq!(a.language().unwrap().as_str(), "en-US")
+	}
+
+	#[test]
+	fn split_by_direction() {
+		let directed = |data: &str, direction| LangString::new(data.into(), None, direction);
+		let a = directed("a", Some(Direction::Ltr)).unwrap();
+		let b = directed("b", Some(Direction::Rtl)).unwrap();
+		let c = directed("c", Some(Direction::Ltr)).unwrap();
+		let d = lang_string("en");
+
+		let groups = LangString::split_by_direction([a.clone(), b.clone(), c.clone(), d.clone()]);
+		assert_eq!(
+			groups,
+			ByDirection {
+				ltr: vec![a, c],
+				rtl: vec![b],
+				none: vec![d]
+			}
+		)
+	}
+}
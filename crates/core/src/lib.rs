@@ -6,6 +6,7 @@ pub mod context;
 mod deserialization;
 mod document;
 pub mod flattening;
+pub mod framing;
 pub mod id;
 mod indexed;
 mod lang_string;
@@ -17,6 +18,8 @@ pub mod quad;
 pub mod rdf;
 mod serialization;
 mod term;
+#[cfg(test)]
+mod test_utils;
 mod ty;
 pub mod utils;
 pub mod warning;
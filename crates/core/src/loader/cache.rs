@@ -0,0 +1,134 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use iref::{Iri, IriBuf};
+
+use super::{Loader, RemoteDocument};
+use crate::LoadingResult;
+
+/// Caching loader.
+///
+/// Wraps another loader and keeps a copy of every document it successfully
+/// loads, so that loading the same IRI again does not query the inner loader.
+/// Failed loads are not cached.
+///
+/// This can be useful to wrap a [`ReqwestLoader`](super::ReqwestLoader), which
+/// makes a new network query each time a document is loaded.
+pub struct CacheLoader<L> {
+	inner: L,
+	cache: Mutex<HashMap<IriBuf, RemoteDocument>>,
+}
+
+impl<L> CacheLoader<L> {
+	/// Creates a new caching loader around the given loader.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			cache: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns a reference to the inner loader.
+	pub fn inner(&self) -> &L {
+		&self.inner
+	}
+
+	/// Returns the number of cached documents.
+	pub fn len(&self) -> usize {
+		self.cache.lock().unwrap().len()
+	}
+
+	/// Checks if no document is cached.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Removes every cached document.
+	pub fn clear(&self) {
+		self.cache.lock().unwrap().clear()
+	}
+
+	/// Returns the inner loader, dropping the cache.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+}
+
+impl<L: Loader> Loader for CacheLoader<L> {
+	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		if let Some(document) = self.cache.lock().unwrap().get(url) {
+			return Ok(document.clone());
+		}
+
+		// The lock is not held while loading, so a concurrent load of the same
+		// IRI may query the inner loader twice.
+		let document = self.inner.load(url).await?;
+		self.cache
+			.lock()
+			.unwrap()
+			.insert(url.to_owned(), document.clone());
+		Ok(document)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{loader::map::EntryNotFound, LoadError};
+	use json_syntax::Parse;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Loader serving a plain JSON document linking to an external context.
+	#[derive(Default)]
+	struct MockLoader {
+		loads: AtomicUsize,
+	}
+
+	impl Loader for MockLoader {
+		async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
+			self.loads.fetch_add(1, Ordering::SeqCst);
+
+			if url.as_str() != "http://example.org/doc" {
+				return Err(LoadError::new(url.to_owned(), EntryNotFound));
+			}
+
+			let (document, _) = json_syntax::Value::parse_str(r#"{ "name": "John" }"#).unwrap();
+			Ok(RemoteDocument::new_full(
+				Some(url.to_owned()),
+				Some("application/json".parse().unwrap()),
+				Some(IriBuf::new("http://example.org/context".to_owned()).unwrap()),
+				Default::default(),
+				document,
+			))
+		}
+	}
+
+	#[test]
+	fn cache_reuse() {
+		let loader = CacheLoader::new(MockLoader::default());
+		let url = Iri::new("http://example.org/doc").unwrap();
+
+		for _ in 0..3 {
+			let document = futures::executor::block_on(loader.load(url)).unwrap();
+			assert_eq!(
+				document.context_url().map(IriBuf::as_str),
+				Some("http://example.org/context")
+			);
+		}
+
+		assert_eq!(loader.inner().loads.load(Ordering::SeqCst), 1);
+		assert_eq!(loader.len(), 1)
+	}
+
+	#[test]
+	fn errors_are_not_cached() {
+		let loader = CacheLoader::new(MockLoader::default());
+		let url = Iri::new("http://example.org/missing").unwrap();
+
+		for _ in 0..2 {
+			assert!(futures::executor::block_on(loader.load(url)).is_err())
+		}
+
+		assert_eq!(loader.inner().loads.load(Ordering::SeqCst), 2);
+		assert!(loader.is_empty())
+	}
+}
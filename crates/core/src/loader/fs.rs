@@ -13,6 +13,10 @@ pub enum Error {
 	#[error("no mount point")]
 	NoMountPoint,
 
+	/// The IRI path escapes the mount point directory.
+	#[error("path escapes the mount point")]
+	PathTraversal,
+
 	/// IO error.
 	#[error("IO: {0}")]
 	IO(std::io::Error),
@@ -49,27 +53,79 @@ impl FsLoader {
 		self.mount_points.push((path.as_ref().into(), url));
 	}
 
+	/// Creates a new file system loader resolving `file:` IRIs relative to
+	/// the given `root` directory.
+	///
+	/// The IRI `file:///a/b.jsonld` is loaded from `{root}/a/b.jsonld`.
+	pub fn with_root<P: AsRef<Path>>(root: P) -> Self {
+		let mut loader = Self::new();
+		loader.mount(IriBuf::new("file:///".to_owned()).unwrap(), root);
+		loader
+	}
+
 	/// Returns the local file path associated to the given `url` if any.
+	///
+	/// Returns `None` if no mount point matches `url`, or if the path of
+	/// `url` escapes the mount point directory.
 	pub fn filepath(&self, url: &Iri) -> Option<PathBuf> {
+		self.resolve(url).ok()
+	}
+
+	fn resolve(&self, url: &Iri) -> Result<PathBuf, Error> {
 		for (path, target_url) in &self.mount_points {
 			if let Some((suffix, _, _)) = url.as_iri_ref().suffix(target_url) {
+				if escapes(url, target_url) {
+					return Err(Error::PathTraversal);
+				}
+
 				let mut filepath = path.clone();
 				for seg in suffix.as_path().segments() {
 					filepath.push(seg.as_str())
 				}
 
-				return Some(filepath);
+				return Ok(filepath);
 			}
 		}
 
-		None
+		Err(Error::NoMountPoint)
+	}
+}
+
+/// Checks if the dot segments of the `url` path climb above the `mount_point`
+/// path.
+///
+/// Dot segments are removed when matching `url` against the mount point, so
+/// an escaping path would otherwise silently resolve inside the mounted
+/// directory.
+fn escapes(url: &Iri, mount_point: &Iri) -> bool {
+	let base = mount_point
+		.path()
+		.segments()
+		.filter(|seg| !seg.as_str().is_empty())
+		.count();
+
+	let mut depth = 0usize;
+	for seg in url.path().segments() {
+		match seg.as_str() {
+			"" | "." => (),
+			".." => {
+				if depth <= base {
+					return true;
+				}
+
+				depth -= 1
+			}
+			_ => depth += 1,
+		}
 	}
+
+	false
 }
 
 impl Loader for FsLoader {
 	async fn load(&self, url: &Iri) -> LoadingResult<IriBuf> {
-		match self.filepath(url) {
-			Some(filepath) => {
+		match self.resolve(url) {
+			Ok(filepath) => {
 				let file = File::open(filepath)
 					.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
 				let mut buf_reader = BufReader::new(file);
@@ -85,7 +141,49 @@ impl Loader for FsLoader {
 					doc,
 				))
 			}
-			None => Err(LoadError::new(url.to_owned(), Error::NoMountPoint)),
+			Err(e) => Err(LoadError::new(url.to_owned(), e)),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn file_root() {
+		let root = std::env::temp_dir().join(format!("json-ld-fs-loader-{}", std::process::id()));
+		std::fs::create_dir_all(root.join("contexts")).unwrap();
+		std::fs::write(
+			root.join("contexts/person.jsonld"),
+			r#"{ "@context": { "name": "http://schema.org/name" } }"#,
+		)
+		.unwrap();
+
+		let loader = FsLoader::with_root(&root);
+
+		let url = Iri::new("file:///contexts/person.jsonld").unwrap();
+		let document = futures::executor::block_on(loader.load(url)).unwrap();
+		assert_eq!(document.url().map(IriBuf::as_str), Some(url.as_str()));
+		assert!(document
+			.document()
+			.as_object()
+			.unwrap()
+			.get("@context")
+			.next()
+			.is_some());
+
+		let escaping = Iri::new("file:///contexts/../../etc/passwd").unwrap();
+		assert_eq!(loader.filepath(escaping), None);
+		assert!(futures::executor::block_on(loader.load(escaping)).is_err());
+
+		// Going up within the root is fine.
+		let url = Iri::new("file:///other/../contexts/person.jsonld").unwrap();
+		assert_eq!(
+			loader.filepath(url),
+			Some(root.join("contexts/person.jsonld"))
+		);
+
+		std::fs::remove_dir_all(root).unwrap()
+	}
+}
@@ -5,11 +5,13 @@ use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
 use static_iref::iri;
 use std::{borrow::Cow, hash::Hash};
 
+pub mod cache;
 pub mod chain;
 pub mod fs;
 pub mod map;
 pub mod none;
 
+pub use cache::CacheLoader;
 pub use chain::ChainLoader;
 pub use fs::FsLoader;
 pub use none::NoLoader;
@@ -0,0 +1,228 @@
+//! Graph merging and deduplication.
+use super::Graph;
+use crate::{Id, IndexedObject, Node, Object};
+use hashbrown::HashMap;
+use indexmap::IndexSet;
+use std::hash::Hash;
+
+/// Graph extension methods.
+///
+/// [`Graph`] being an alias for [`IndexSet`], these operations are provided
+/// as an extension trait.
+pub trait GraphExt<T, B> {
+	/// Merges the objects of `other` into this graph.
+	///
+	/// Node objects sharing the same valid identifier are merged into a single
+	/// node: their types are unioned, their property values are appended and
+	/// the members of their `@graph` are merged the same way. Nodes without a
+	/// valid identifier and value objects are appended as-is.
+	fn merge(&mut self, other: Self);
+
+	/// Removes all but the first of equivalent objects, preserving the order
+	/// of the remaining objects.
+	///
+	/// Objects are compared with [`Object::is_equivalent`], and must have the
+	/// same index.
+	fn dedup(&mut self);
+}
+
+impl<T: Clone + Hash + Eq, B: Clone + Hash + Eq> GraphExt<T, B> for Graph<T, B> {
+	fn merge(&mut self, other: Self) {
+		*self = merge_objects(std::mem::take(self), other)
+	}
+
+	fn dedup(&mut self) {
+		let mut kept: Vec<IndexedObject<T, B>> = Vec::with_capacity(self.len());
+		for object in std::mem::take(self) {
+			let duplicate = kept
+				.iter()
+				.any(|o| o.index() == object.index() && o.is_equivalent(&object));
+
+			if !duplicate {
+				kept.push(object)
+			}
+		}
+
+		*self = kept.into_iter().collect()
+	}
+}
+
+pub(crate) fn merge_objects<T: Clone + Hash + Eq, B: Clone + Hash + Eq>(
+	a: IndexSet<IndexedObject<T, B>>,
+	b: IndexSet<IndexedObject<T, B>>,
+) -> IndexSet<IndexedObject<T, B>> {
+	let mut objects: Vec<IndexedObject<T, B>> = Vec::with_capacity(a.len() + b.len());
+	let mut nodes: HashMap<Id<T, B>, usize> = HashMap::new();
+
+	for object in a.into_iter().chain(b) {
+		let id = match object.inner().as_node().and_then(|node| node.id.as_ref()) {
+			Some(id) if id.is_valid() => id.clone(),
+			_ => {
+				objects.push(object);
+				continue;
+			}
+		};
+
+		match nodes.get(&id) {
+			Some(&i) => {
+				let target = &mut objects[i];
+				let (object, index) = object.into_parts();
+
+				if target.index().is_none() {
+					target.set_index(index)
+				}
+
+				if let Object::Node(node) = object {
+					merge_node(target.inner_mut().as_node_mut().unwrap(), *node)
+				}
+			}
+			None => {
+				nodes.insert(id, objects.len());
+				objects.push(object)
+			}
+		}
+	}
+
+	objects.into_iter().collect()
+}
+
+fn merge_node<T: Clone + Hash + Eq, B: Clone + Hash + Eq>(
+	target: &mut Node<T, B>,
+	other: Node<T, B>,
+) {
+	if let Some(types) = other.types {
+		let target_types = target.types_mut_or_default();
+		for ty in types {
+			if !target_types.contains(&ty) {
+				target_types.push(ty)
+			}
+		}
+	}
+
+	if let Some(graph) = other.graph {
+		target.graph = Some(match target.graph.take() {
+			Some(target_graph) => merge_objects(target_graph, graph),
+			None => graph,
+		})
+	}
+
+	if let Some(included) = other.included {
+		target.included_mut_or_default().extend(included)
+	}
+
+	for (prop, objects) in other.properties {
+		target.properties_mut().insert_all_unique(prop, objects)
+	}
+
+	if let Some(reverse_properties) = other.reverse_properties {
+		let target_reverse_properties = target.reverse_properties_mut_or_default();
+		for (prop, nodes) in reverse_properties {
+			target_reverse_properties.insert_all_unique(prop, nodes)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TryFromJson;
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	fn graph(json: &str) -> Graph<iref::IriBuf, rdf_types::BlankIdBuf> {
+		let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+		json.into_array()
+			.unwrap()
+			.into_iter()
+			.map(|item| IndexedObject::try_from_json_in(no_vocabulary_mut(), item).unwrap())
+			.collect()
+	}
+
+	#[test]
+	fn merge_shared_node() {
+		let mut a = graph(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/Person"],
+					"http://example.org/name": [{ "@value": "Alice" }]
+				},
+				{ "@id": "http://example.org/b" }
+			]"#,
+		);
+
+		let b = graph(
+			r#"[{
+				"@id": "http://example.org/a",
+				"@type": ["http://example.org/Agent"],
+				"http://example.org/age": [{ "@value": 42 }]
+			}]"#,
+		);
+
+		a.merge(b);
+
+		let expected = graph(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/Person", "http://example.org/Agent"],
+					"http://example.org/name": [{ "@value": "Alice" }],
+					"http://example.org/age": [{ "@value": 42 }]
+				},
+				{ "@id": "http://example.org/b" }
+			]"#,
+		);
+
+		assert_eq!(a, expected)
+	}
+
+	#[test]
+	fn merge_overlapping_values() {
+		let mut a = graph(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/name": [{ "@value": "Alice" }],
+				"@reverse": { "http://example.org/knows": [{ "@id": "http://example.org/b" }] }
+			}]"#,
+		);
+
+		let b = graph(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/name": [{ "@value": "Alice" }, { "@value": "Alicia" }],
+				"@reverse": { "http://example.org/knows": [{ "@id": "http://example.org/b" }] }
+			}]"#,
+		);
+
+		a.merge(b);
+
+		let expected = graph(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/name": [{ "@value": "Alice" }, { "@value": "Alicia" }],
+				"@reverse": { "http://example.org/knows": [{ "@id": "http://example.org/b" }] }
+			}]"#,
+		);
+
+		assert_eq!(a, expected)
+	}
+
+	#[test]
+	fn dedup() {
+		let mut g = graph(
+			r#"[
+				{ "@value": 1 },
+				{ "@value": 1.0 },
+				{ "@value": 1, "@index": "i" },
+				{ "@value": 2 }
+			]"#,
+		);
+
+		g.dedup();
+
+		assert_eq!(
+			g,
+			graph(r#"[{ "@value": 1 }, { "@value": 1, "@index": "i" }, { "@value": 2 }]"#)
+		)
+	}
+}
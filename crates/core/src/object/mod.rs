@@ -10,15 +10,20 @@ use rdf_types::{BlankIdBuf, Generator, Subject, Vocabulary, VocabularyMut};
 use smallvec::SmallVec;
 use std::hash::Hash;
 
+pub mod graph;
 pub mod list;
 mod mapped_eq;
 pub mod node;
+mod ordering;
 mod typ;
 pub mod value;
 
+pub use graph::GraphExt;
 pub use list::List;
 pub use mapped_eq::MappedEq;
 pub use node::{Graph, IndexedNode, Node, Nodes};
+pub use ordering::compare_ids;
+pub(crate) use ordering::sort_set;
 pub use typ::{Type, TypeRef};
 pub use value::{Literal, Value};
 
@@ -190,6 +195,30 @@ impl<T, B> Object<T, B> {
 		}
 	}
 
+	/// Returns an iterator over the `@type` entry of the object.
+	///
+	/// Unlike [`Self::types`], the datatype of value objects is not included:
+	/// the iterator is empty unless the object is a node object.
+	pub fn node_types(&self) -> std::slice::Iter<'_, Id<T, B>> {
+		match self {
+			Self::Node(node) => node.types().iter(),
+			_ => [].iter(),
+		}
+	}
+
+	/// Checks if the object is a node object with the given type.
+	///
+	/// Always returns `false` for value and list objects.
+	pub fn has_type<U>(&self, ty: &U) -> bool
+	where
+		Id<T, B>: PartialEq<U>,
+	{
+		match self {
+			Self::Node(node) => node.has_type(ty),
+			_ => false,
+		}
+	}
+
 	/// Identifier of the object as an IRI.
 	///
 	/// If the object is a node identified by an IRI, returns this IRI.
@@ -392,6 +421,23 @@ impl<T, B> Object<T, B> {
 		}
 	}
 
+	/// JSON-LD value equivalence.
+	///
+	/// Unlike [`Self::equivalent`], value objects are compared with
+	/// [`Value::is_equivalent`]: numbers are compared by value, so `1` is
+	/// equivalent to `1.0`. Node and list objects are compared with
+	/// [`Self::equivalent`].
+	pub fn is_equivalent(&self, other: &Self) -> bool
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		match (self, other) {
+			(Self::Value(a), Self::Value(b)) => a.is_equivalent(b),
+			_ => self.equivalent(other),
+		}
+	}
+
 	/// Returns an iterator over the entries of JSON representation of the
 	/// object.
 	pub fn entries(&self) -> Entries<T, B> {
@@ -1070,6 +1116,10 @@ impl<'a, T, B> FragmentRef<'a, T, B> {
 			Self::IndexedNodeList(l) => SubFragments::IndexedNodeList(l.iter()),
 			Self::ValueFragment(i) => SubFragments::Value(i.sub_fragments()),
 			Self::NodeFragment(i) => SubFragments::Node(i.sub_fragments()),
+			Self::ListFragment(list::FragmentRef::Entry(items)) => {
+				SubFragments::ListEntry(Some(()), Some(items))
+			}
+			Self::ListFragment(list::FragmentRef::Value(items)) => SubFragments::List(items.iter()),
 			_ => SubFragments::None,
 		}
 	}
@@ -1106,6 +1156,8 @@ pub enum SubFragments<'a, T, B> {
 	Value(value::SubFragments<'a, T>),
 	Node(node::SubFragments<'a, T, B>),
 	IndexedNodeList(std::slice::Iter<'a, IndexedNode<T, B>>),
+	ListEntry(Option<()>, Option<&'a [IndexedObject<T, B>]>),
+	List(std::slice::Iter<'a, IndexedObject<T, B>>),
 }
 
 impl<'a, T, B> Iterator for SubFragments<'a, T, B> {
@@ -1125,6 +1177,14 @@ impl<'a, T, B> Iterator for SubFragments<'a, T, B> {
 			Self::Value(i) => i.next().map(FragmentRef::ValueFragment),
 			Self::Node(i) => i.next(),
 			Self::IndexedNodeList(i) => i.next().map(FragmentRef::IndexedNode),
+			Self::ListEntry(k, v) => k
+				.take()
+				.map(|()| FragmentRef::ListFragment(list::FragmentRef::Key))
+				.or_else(|| {
+					v.take()
+						.map(|v| FragmentRef::ListFragment(list::FragmentRef::Value(v)))
+				}),
+			Self::List(i) => i.next().map(FragmentRef::IndexedObject),
 		}
 	}
 }
@@ -1163,3 +1223,55 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> IntoJsonWithContext<N> for Objec
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	fn object(json: &str) -> Object {
+		let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+		Object::try_from_json_in(no_vocabulary_mut(), json).unwrap()
+	}
+
+	#[test]
+	fn equivalent_numbers() {
+		let a = object(r#"{ "@value": 1 }"#);
+		let b = object(r#"{ "@value": 1.0 }"#);
+
+		assert_ne!(a, b);
+		assert!(a.is_equivalent(&b));
+		assert!(!a.is_equivalent(&object(r#"{ "@value": 2 }"#)));
+		assert!(!a.is_equivalent(&object(r#"{ "@value": "1" }"#)))
+	}
+
+	#[test]
+	fn equivalent_language_tags() {
+		let a = object(r#"{ "@value": "colour", "@language": "en-GB" }"#);
+		let b = object(r#"{ "@value": "colour", "@language": "en-gb" }"#);
+
+		assert!(a.is_equivalent(&b));
+		assert!(!a.is_equivalent(&object(r#"{ "@value": "colour", "@language": "en" }"#)))
+	}
+
+	#[test]
+	fn node_types() {
+		let iri = |iri: &str| Id::iri(IriBuf::new(iri.to_owned()).unwrap());
+		let person = iri("http://example.org/Person");
+		let agent = iri("http://example.org/Agent");
+		let int = iri("http://www.w3.org/2001/XMLSchema#int");
+
+		let node =
+			object(r#"{ "@type": ["http://example.org/Person", "http://example.org/Agent"] }"#);
+		assert_eq!(node.node_types().collect::<Vec<_>>(), [&person, &agent]);
+		assert!(node.has_type(&agent));
+		assert!(!node.has_type(&int));
+
+		let value =
+			object(r#"{ "@value": "42", "@type": "http://www.w3.org/2001/XMLSchema#int" }"#);
+		assert_eq!(value.types().count(), 1);
+		assert_eq!(value.node_types().count(), 0);
+		assert!(!value.has_type(&int))
+	}
+}
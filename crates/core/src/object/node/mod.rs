@@ -17,6 +17,9 @@ pub use multiset::Multiset;
 pub use properties::Properties;
 pub use reverse_properties::ReverseProperties;
 
+use properties::PropertyObjects;
+use reverse_properties::{ReverseBindingRef, ReversePropertyNodes};
+
 pub type Graph<T, B> = IndexSet<IndexedObject<T, B>>;
 
 pub type Included<T, B> = IndexSet<IndexedNode<T, B>>;
@@ -413,6 +416,24 @@ impl<T, B> Node<T, B> {
 		self.reverse_properties = reverse_properties
 	}
 
+	/// Returns an iterator over the reverse properties of the node and their
+	/// associated nodes.
+	///
+	/// The iterator is empty if the node has no `@reverse` entry.
+	pub fn reverse_iter(&self) -> impl '_ + Iterator<Item = ReverseBindingRef<'_, T, B>> {
+		self.reverse_properties
+			.iter()
+			.flat_map(ReverseProperties::iter)
+	}
+
+	/// Returns the number of reverse properties of the node.
+	pub fn reverse_len(&self) -> usize {
+		self.reverse_properties
+			.as_ref()
+			.map(ReverseProperties::len)
+			.unwrap_or_default()
+	}
+
 	/// Tests if the node is an unnamed graph object.
 	///
 	/// Returns `true` is the only field of the object is a `@graph` field.
@@ -567,6 +588,46 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 		self.properties.get(prop)
 	}
 
+	/// Returns the set of objects associated to the node with the given
+	/// property, if any.
+	#[inline(always)]
+	pub fn properties_for<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&self,
+		prop: &Q,
+	) -> Option<&PropertyObjects<T, B>> {
+		self.properties.get_objects(prop)
+	}
+
+	/// Returns a mutable reference to the set of objects associated to the
+	/// node with the given property, if any.
+	#[inline(always)]
+	pub fn properties_for_mut<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&mut self,
+		prop: &Q,
+	) -> Option<&mut PropertyObjects<T, B>> {
+		self.properties.get_objects_mut(prop)
+	}
+
+	/// Returns the set of nodes associated to the node with the given reverse
+	/// property, if any.
+	#[inline(always)]
+	pub fn reverse_properties_for<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&self,
+		prop: &Q,
+	) -> Option<&ReversePropertyNodes<T, B>> {
+		self.reverse_properties.as_ref()?.get_nodes(prop)
+	}
+
+	/// Returns a mutable reference to the set of nodes associated to the node
+	/// with the given reverse property, if any.
+	#[inline(always)]
+	pub fn reverse_properties_for_mut<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&mut self,
+		prop: &Q,
+	) -> Option<&mut ReversePropertyNodes<T, B>> {
+		self.reverse_properties.as_mut()?.get_nodes_mut(prop)
+	}
+
 	/// Get one of the objects associated to the node with the given property.
 	///
 	/// If multiple objects are attached to the node with this property, there are no guaranties
@@ -601,6 +662,16 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 		self.properties.insert_all(prop, values)
 	}
 
+	/// Associates the given node to this node through the given reverse
+	/// property.
+	///
+	/// The `@reverse` entry is created if necessary. Duplicate nodes are not
+	/// removed.
+	#[inline(always)]
+	pub fn insert_reverse(&mut self, prop: Id<T, B>, node: IndexedNode<T, B>) {
+		self.reverse_properties_or_default().insert(prop, node)
+	}
+
 	pub fn reverse_properties_or_insert(
 		&mut self,
 		props: ReverseProperties<T, B>,
@@ -707,7 +778,7 @@ impl<T, B> Relabel<T, B> for Node<T, B> {
 impl<T: Eq + Hash, B: Eq + Hash> PartialEq for Node<T, B> {
 	fn eq(&self, other: &Self) -> bool {
 		self.id.eq(&other.id)
-			&& multiset::compare_unordered_opt(self.types.as_deref(), other.types.as_deref())
+			&& multiset::compare_unordered_hashed_opt(self.types.as_deref(), other.types.as_deref())
 			&& self.graph.as_ref() == other.graph.as_ref()
 			&& self.included.as_ref() == other.included.as_ref()
 			&& self.properties.eq(&other.properties)
@@ -1341,3 +1412,70 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> IntoJsonWithContext<N> for Node<
 		obj.into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	fn iri(iri: &str) -> Id {
+		Id::iri(IriBuf::new(iri.to_owned()).unwrap())
+	}
+
+	#[test]
+	fn properties_for() {
+		let (json, _) = json_syntax::Value::parse_str(
+			r#"{
+				"@id": "http://example.org/a",
+				"http://example.org/name": [{ "@value": "Alice" }, { "@value": "Alicia" }],
+				"@reverse": {
+					"http://example.org/knows": [{ "@id": "http://example.org/b" }]
+				}
+			}"#,
+		)
+		.unwrap();
+		let mut node: Node = IndexedObject::try_from_json_in(no_vocabulary_mut(), json)
+			.unwrap()
+			.into_inner()
+			.into_node()
+			.unwrap();
+
+		let name = iri("http://example.org/name");
+		let knows = iri("http://example.org/knows");
+		assert_eq!(node.properties_for(&name).map(Multiset::len), Some(2));
+		assert!(node.properties_for(&knows).is_none());
+		assert_eq!(
+			node.reverse_properties_for(&knows).map(Multiset::len),
+			Some(1)
+		);
+		assert!(node.reverse_properties_for(&name).is_none());
+
+		node.properties_for_mut(&name)
+			.unwrap()
+			.retain(|o| o.as_str() == Some("Alice"));
+		assert_eq!(node.get(&name).count(), 1)
+	}
+
+	#[test]
+	fn reverse_properties_api() {
+		let knows = iri("http://example.org/knows");
+		let parent = iri("http://example.org/parent");
+		let reverse = |id: &str| Indexed::new(Node::with_id(iri(id)), None);
+
+		let mut node: Node = Node::with_id(iri("http://example.org/a"));
+		assert_eq!(node.reverse_len(), 0);
+		assert_eq!(node.reverse_iter().count(), 0);
+
+		node.insert_reverse(knows.clone(), reverse("http://example.org/b"));
+		node.insert_reverse(knows.clone(), reverse("http://example.org/c"));
+		node.insert_reverse(parent.clone(), reverse("http://example.org/d"));
+		assert_eq!(node.reverse_len(), 2);
+
+		let entries: Vec<_> = node
+			.reverse_iter()
+			.map(|(prop, nodes)| (prop.clone(), nodes.len()))
+			.collect();
+		assert_eq!(entries, [(knows, 2), (parent, 1)])
+	}
+}
@@ -1,13 +1,87 @@
-use std::hash::{BuildHasher, Hash};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
 
+/// Builder of [`DeterministicHasher`].
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DeterministicHasherBuilder;
 
 impl BuildHasher for DeterministicHasherBuilder {
-	type Hasher = std::collections::hash_map::DefaultHasher;
+	type Hasher = DeterministicHasher;
 
 	fn build_hasher(&self) -> Self::Hasher {
-		Self::Hasher::new()
+		DeterministicHasher::default()
+	}
+}
+
+/// 64-bit FNV-1a hasher.
+///
+/// Unlike the standard library default hasher, whose algorithm may change
+/// between Rust versions, the hashes produced by this hasher are stable
+/// across toolchains and platforms: integers are always written in
+/// little-endian order, and `usize` values as `u64`.
+#[derive(Debug, Clone, Copy)]
+pub struct DeterministicHasher(u64);
+
+impl DeterministicHasher {
+	const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+	const PRIME: u64 = 0x100000001b3;
+}
+
+impl Default for DeterministicHasher {
+	fn default() -> Self {
+		Self(Self::OFFSET_BASIS)
+	}
+}
+
+impl Hasher for DeterministicHasher {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		for b in bytes {
+			self.0 = (self.0 ^ *b as u64).wrapping_mul(Self::PRIME)
+		}
+	}
+
+	fn write_u16(&mut self, i: u16) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_u32(&mut self, i: u32) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_u64(&mut self, i: u64) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_u128(&mut self, i: u128) {
+		self.write(&i.to_le_bytes())
+	}
+
+	fn write_usize(&mut self, i: usize) {
+		self.write_u64(i as u64)
+	}
+
+	fn write_i16(&mut self, i: i16) {
+		self.write_u16(i as u16)
+	}
+
+	fn write_i32(&mut self, i: i32) {
+		self.write_u32(i as u32)
+	}
+
+	fn write_i64(&mut self, i: i64) {
+		self.write_u64(i as u64)
+	}
+
+	fn write_i128(&mut self, i: i128) {
+		self.write_u128(i as u128)
+	}
+
+	fn write_isize(&mut self, i: isize) {
+		self.write_u64(i as u64)
 	}
 }
 
@@ -47,6 +121,41 @@ impl<T, S> Multiset<T, S> {
 		}
 	}
 
+	/// Creates an empty multiset using the given hasher builder.
+	///
+	/// The hasher builder is used to compute the hash of the multiset. Unlike
+	/// [`Self::new`], it does not require `S: Default`.
+	///
+	/// ```
+	/// use std::collections::hash_map::RandomState;
+	/// use json_ld_core_next::object::node::Multiset;
+	///
+	/// // Seeded SipHash hasher.
+	/// let mut set = Multiset::with_hasher(RandomState::new());
+	/// set.insert("value");
+	/// assert!(set.contains(&"value"));
+	/// ```
+	pub fn with_hasher(hasher: S) -> Self {
+		Self {
+			data: Vec::new(),
+			hasher,
+		}
+	}
+
+	/// Creates an empty multiset with the given capacity, using the given
+	/// hasher builder.
+	pub fn with_capacity_and_hasher(cap: usize, hasher: S) -> Self {
+		Self {
+			data: Vec::with_capacity(cap),
+			hasher,
+		}
+	}
+
+	/// Returns a reference to the multiset's hasher builder.
+	pub fn hasher(&self) -> &S {
+		&self.hasher
+	}
+
 	pub fn len(&self) -> usize {
 		self.data.len()
 	}
@@ -62,6 +171,14 @@ impl<T, S> Multiset<T, S> {
 		self.data.contains(value)
 	}
 
+	/// Returns the number of occurrences of the given value.
+	pub fn count_of(&self, value: &T) -> usize
+	where
+		T: PartialEq,
+	{
+		self.data.iter().filter(|v| *v == value).count()
+	}
+
 	pub fn iter(&self) -> core::slice::Iter<T> {
 		self.data.iter()
 	}
@@ -74,6 +191,76 @@ impl<T, S> Multiset<T, S> {
 		&self.data
 	}
 
+	/// Returns the value at the given position in the iteration order, if
+	/// any.
+	///
+	/// Positions reflect the insertion order, or the last [`Self::sort`].
+	/// They do not affect equality nor hashing.
+	pub fn get(&self, index: usize) -> Option<&T> {
+		self.data.get(index)
+	}
+
+	/// Sorts the values with the given comparison function.
+	///
+	/// The order of the values does not affect equality nor hashing, so this
+	/// only changes the iteration order.
+	pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> std::cmp::Ordering) {
+		self.data.sort_by(compare)
+	}
+
+	/// Sorts the values.
+	///
+	/// The order of the values does not affect equality nor hashing, so this
+	/// only changes the iteration order.
+	pub fn sort(&mut self)
+	where
+		T: Ord,
+	{
+		self.data.sort()
+	}
+
+	/// Removes one occurrence of the given value, preserving the order of the
+	/// remaining values.
+	///
+	/// Returns `true` if an occurrence was found and removed.
+	pub fn remove(&mut self, value: &T) -> bool
+	where
+		T: PartialEq,
+	{
+		match self.data.iter().position(|v| v == value) {
+			Some(i) => {
+				self.data.remove(i);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Retains only the values for which `f` returns `true`, preserving their
+	/// order.
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+		self.data.retain(f)
+	}
+
+	/// Removes the values for which `f` returns `true` and returns them, with a
+	/// default hasher.
+	///
+	/// Both the removed and the remaining values keep their order.
+	pub fn drain_filter<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Self
+	where
+		S: Default,
+	{
+		let (removed, kept) = std::mem::take(&mut self.data)
+			.into_iter()
+			.partition(|value| f(value));
+		self.data = kept;
+
+		Multiset {
+			data: removed,
+			hasher: S::default(),
+		}
+	}
+
 	// pub fn into_stripped(self) -> Multiset<locspan::Stripped<T>, S> {
 	// 	Multiset { data: unsafe { core::mem::transmute(self.data) }, hasher: self.hasher }
 	// }
@@ -106,6 +293,125 @@ impl<T: Hash, S: BuildHasher> Multiset<T, S> {
 	}
 }
 
+impl<T: Hash + Eq, S> Multiset<T, S> {
+	/// Checks that this multiset and `other` contain the same values with the
+	/// same multiplicities.
+	///
+	/// This is equivalent to `==`, but runs in linear time by bucketing the
+	/// values by hash instead of comparing every pair of values.
+	pub fn hashed_eq<P>(&self, other: &Multiset<T, P>) -> bool {
+		compare_unordered_hashed(&self.data, &other.data)
+	}
+
+	/// Returns the number of occurrences of each distinct value.
+	pub fn counts(&self) -> HashMap<&T, usize> {
+		let mut counts = HashMap::with_capacity(self.data.len());
+		for item in &self.data {
+			*counts.entry(item).or_default() += 1
+		}
+
+		counts
+	}
+
+	/// Removes all but the first occurrence of each distinct value, preserving
+	/// the order of the remaining values.
+	pub fn dedup(&mut self) {
+		let keep: Vec<bool> = {
+			let mut seen = HashSet::with_capacity(self.data.len());
+			self.data.iter().map(|item| seen.insert(item)).collect()
+		};
+
+		let mut keep = keep.into_iter();
+		self.data.retain(|_| keep.next().unwrap())
+	}
+
+	/// Returns an iterator over the first occurrence of each distinct value,
+	/// in encounter order.
+	pub fn distinct(&self) -> impl '_ + Iterator<Item = &T> {
+		let mut seen = HashSet::with_capacity(self.data.len());
+		self.data.iter().filter(move |item| seen.insert(*item))
+	}
+}
+
+impl<T: Hash + Eq + Clone, S: Clone> Multiset<T, S> {
+	/// Returns the union of this multiset and `other`.
+	///
+	/// Each value occurs as many times as its maximum multiplicity in both
+	/// multisets.
+	pub fn union<P>(&self, other: &Multiset<T, P>) -> Self {
+		let mut result = self.clone();
+		let mut remaining = self.counts();
+
+		for item in &other.data {
+			if !take(&mut remaining, item) {
+				result.data.push(item.clone())
+			}
+		}
+
+		result
+	}
+
+	/// Returns the intersection of this multiset and `other`.
+	///
+	/// Each value occurs as many times as its minimum multiplicity in both
+	/// multisets.
+	pub fn intersection<P>(&self, other: &Multiset<T, P>) -> Self {
+		let mut remaining = other.counts();
+		let data = self
+			.data
+			.iter()
+			.filter(|item| take(&mut remaining, item))
+			.cloned()
+			.collect();
+
+		Self {
+			data,
+			hasher: self.hasher.clone(),
+		}
+	}
+
+	/// Returns the difference between this multiset and `other`.
+	///
+	/// The multiplicity of each value is its multiplicity in this multiset
+	/// minus its multiplicity in `other`, if positive.
+	pub fn difference<P>(&self, other: &Multiset<T, P>) -> Self {
+		let mut remaining = other.counts();
+		let data = self
+			.data
+			.iter()
+			.filter(|item| !take(&mut remaining, item))
+			.cloned()
+			.collect();
+
+		Self {
+			data,
+			hasher: self.hasher.clone(),
+		}
+	}
+}
+
+/// Consumes one occurrence of `item` from `counts`, if any.
+fn take<T: Hash + Eq>(counts: &mut HashMap<&T, usize>, item: &T) -> bool {
+	match counts.get_mut(item) {
+		Some(count) if *count > 0 => {
+			*count -= 1;
+			true
+		}
+		_ => false,
+	}
+}
+
+/// Positional access, see [`Multiset::get`].
+///
+/// Panics if the index is out of bounds.
+impl<T, S> std::ops::Index<usize> for Multiset<T, S> {
+	type Output = T;
+
+	fn index(&self, index: usize) -> &T {
+		&self.data[index]
+	}
+}
+
 impl<'a, T, S> IntoIterator for &'a Multiset<T, S> {
 	type Item = &'a T;
 	type IntoIter = core::slice::Iter<'a, T>;
@@ -145,6 +451,21 @@ impl<T: Hash, S: Default + BuildHasher> FromIterator<T> for Multiset<T, S> {
 	}
 }
 
+impl<T: Hash, S: Default + BuildHasher> From<Vec<T>> for Multiset<T, S> {
+	fn from(data: Vec<T>) -> Self {
+		Self {
+			data,
+			hasher: S::default(),
+		}
+	}
+}
+
+impl<T: Hash, S: Default + BuildHasher, const N: usize> From<[T; N]> for Multiset<T, S> {
+	fn from(items: [T; N]) -> Self {
+		Vec::from(items).into()
+	}
+}
+
 impl<T: Hash, S: BuildHasher> Extend<T> for Multiset<T, S> {
 	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
 		for item in iter {
@@ -181,9 +502,36 @@ pub(crate) fn compare_unordered<T: PartialEq<U>, U>(a: &[T], b: &[U]) -> bool {
 	}
 }
 
-pub(crate) fn compare_unordered_opt<T: PartialEq<U>, U>(a: Option<&[T]>, b: Option<&[U]>) -> bool {
+/// Under this length, the quadratic comparison is faster than hashing.
+const HASHED_COMPARISON_THRESHOLD: usize = 8;
+
+pub(crate) fn compare_unordered_hashed<T: Hash + Eq>(a: &[T], b: &[T]) -> bool {
+	if a.len() == b.len() {
+		if a.len() < HASHED_COMPARISON_THRESHOLD {
+			return compare_unordered(a, b);
+		}
+
+		let mut counts: HashMap<&T, usize> = HashMap::with_capacity(a.len());
+		for item in a {
+			*counts.entry(item).or_default() += 1
+		}
+
+		for item in b {
+			match counts.get_mut(item) {
+				Some(count) if *count > 0 => *count -= 1,
+				_ => return false,
+			}
+		}
+
+		true
+	} else {
+		false
+	}
+}
+
+pub(crate) fn compare_unordered_hashed_opt<T: Hash + Eq>(a: Option<&[T]>, b: Option<&[T]>) -> bool {
 	match (a, b) {
-		(Some(a), Some(b)) => compare_unordered(a, b),
+		(Some(a), Some(b)) => compare_unordered_hashed(a, b),
 		(None, None) => true,
 		_ => false,
 	}
@@ -212,3 +560,225 @@ impl<T: IntoJsonWithContext<N>, S, N> IntoJsonWithContext<N> for Multiset<T, S>
 		)
 	}
 }
+
+impl<T: Ord, S> Multiset<T, S> {
+	/// Converts this multiset into a JSON array of its values, in sorted
+	/// order.
+	///
+	/// Unlike [`IntoJsonWithContext::into_json_with`], which follows the
+	/// iteration order, the output only depends on the values, making it
+	/// suitable for byte-stable serialization.
+	pub fn into_json_sorted_with<N>(mut self, vocabulary: &N) -> json_syntax::Value
+	where
+		T: IntoJsonWithContext<N>,
+	{
+		self.data.sort();
+		self.into_json_with(vocabulary)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, S> serde::Serialize for Multiset<T, S> {
+	fn serialize<E>(&self, serializer: E) -> Result<E::Ok, E::Error>
+	where
+		E: serde::Serializer,
+	{
+		self.data.serialize(serializer)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, S> serde::Deserialize<'de> for Multiset<T, S>
+where
+	T: serde::Deserialize<'de> + Hash,
+	S: Default + BuildHasher,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let data: Vec<T> = Vec::deserialize(deserializer)?;
+		Ok(data.into_iter().collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{DeterministicHasherBuilder, Multiset};
+	use json_ld_syntax_next::IntoJsonWithContext;
+	use std::hash::BuildHasher;
+
+	#[test]
+	fn stable_hash() {
+		// FNV-1a test vector.
+		assert_eq!(
+			DeterministicHasherBuilder.hash_one(b'a'),
+			0xaf63dc4c8601ec8c
+		);
+
+		let a: Multiset<u32> = [1, 2, 3].into_iter().collect();
+		let b: Multiset<u32> = [3, 1, 2].into_iter().collect();
+		assert_eq!(DeterministicHasherBuilder.hash_one(&a), 0x10c29d25e7b81e03);
+		assert_eq!(
+			DeterministicHasherBuilder.hash_one(&a),
+			DeterministicHasherBuilder.hash_one(&b)
+		)
+	}
+
+	#[test]
+	fn hashed_eq_respects_multiplicity() {
+		let a: Multiset<u32> = [1, 1, 2, 3, 4, 5, 6, 7, 8].into_iter().collect();
+		let b: Multiset<u32> = [8, 7, 6, 5, 4, 3, 2, 1, 1].into_iter().collect();
+		let c: Multiset<u32> = [1, 2, 2, 3, 4, 5, 6, 7, 8].into_iter().collect();
+
+		assert!(a.hashed_eq(&b));
+		assert!(!a.hashed_eq(&c));
+		assert_eq!(a == b, a.hashed_eq(&b));
+		assert_eq!(a == c, a.hashed_eq(&c));
+	}
+
+	#[test]
+	fn remove_single_occurrence() {
+		let mut set: Multiset<&str> = ["a", "b", "a", "c"].into_iter().collect();
+
+		assert!(set.remove(&"a"));
+		assert_eq!(set.as_slice(), &["b", "a", "c"]);
+		assert!(!set.remove(&"d"));
+		assert_eq!(set.len(), 3);
+	}
+
+	#[test]
+	fn retain_none() {
+		let mut set: Multiset<&str> = ["a", "b", "a"].into_iter().collect();
+		set.retain(|_| false);
+		assert!(set.is_empty())
+	}
+
+	#[test]
+	fn drain_filter() {
+		let mut set: Multiset<&str> = ["en:a", "b", "en:c", "d", "en:a"].into_iter().collect();
+		let tagged = set.drain_filter(|s| s.starts_with("en:"));
+
+		assert_eq!(tagged.as_slice(), ["en:a", "en:c", "en:a"]);
+		assert_eq!(set.as_slice(), ["b", "d"])
+	}
+
+	#[test]
+	fn positional_access() {
+		let mut set: Multiset<&str> = ["c", "a", "b"].into_iter().collect();
+		assert_eq!(set.get(0), Some(&"c"));
+		assert_eq!(set.get(3), None);
+
+		set.sort();
+		assert_eq!(set[0], "a");
+		assert_eq!(set[2], "c");
+		assert_eq!(set.get(3), None)
+	}
+
+	#[test]
+	#[should_panic]
+	fn out_of_bounds_index() {
+		let set: Multiset<&str> = ["a"].into_iter().collect();
+		let _ = set[1];
+	}
+
+	#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+	struct Str(&'static str);
+
+	impl IntoJsonWithContext<()> for Str {
+		fn into_json_with(self, _: &()) -> json_syntax::Value {
+			json_syntax::Value::String(self.0.into())
+		}
+	}
+
+	#[test]
+	fn into_json_sorted() {
+		let set: Multiset<Str> = ["c", "a", "b", "a"].map(Str).into();
+		assert_eq!(
+			set.into_json_sorted_with(&()),
+			json_syntax::json!(["a", "a", "b", "c"])
+		)
+	}
+
+	#[test]
+	fn counts() {
+		let set: Multiset<&str> = ["a", "b", "a", "c", "a"].into_iter().collect();
+		let counts = set.counts();
+
+		assert_eq!(counts.len(), 3);
+		assert_eq!(counts[&"a"], 3);
+		assert_eq!(counts[&"b"], 1);
+		assert_eq!(counts[&"c"], 1);
+		assert_eq!(set.count_of(&"a"), 3);
+		assert_eq!(set.count_of(&"d"), 0);
+	}
+
+	#[test]
+	fn dedup() {
+		let mut set: Multiset<&str> = ["a", "b", "a", "c", "b", "a", "d"].into_iter().collect();
+
+		let distinct: Vec<_> = set.distinct().copied().collect();
+		assert_eq!(distinct, ["a", "b", "c", "d"]);
+		assert_eq!(set.len(), 7);
+
+		set.dedup();
+		assert_eq!(set.as_slice(), &["a", "b", "c", "d"]);
+	}
+
+	#[test]
+	fn sort() {
+		let unsorted: Multiset<u32> = [3, 1, 2, 1].into_iter().collect();
+
+		let mut set = unsorted.clone();
+		set.sort();
+		assert_eq!(set.as_slice(), &[1, 1, 2, 3]);
+		assert_eq!(set, unsorted);
+
+		set.sort_by(|a, b| b.cmp(a));
+		assert_eq!(set.as_slice(), &[3, 2, 1, 1]);
+		assert_eq!(set, unsorted);
+		assert_eq!(
+			DeterministicHasherBuilder.hash_one(&set),
+			DeterministicHasherBuilder.hash_one(&unsorted)
+		)
+	}
+
+	#[test]
+	fn algebra() {
+		let a: Multiset<&str> = ["a", "a", "b"].into_iter().collect();
+		let b: Multiset<&str> = ["a", "b", "b"].into_iter().collect();
+		let c: Multiset<&str> = ["a", "c"].into_iter().collect();
+
+		let expected: Multiset<&str> = ["a", "b"].into_iter().collect();
+		assert_eq!(a.intersection(&b), expected);
+		assert_eq!(a.difference(&c), expected);
+
+		let expected: Multiset<&str> = ["a", "a", "b", "b"].into_iter().collect();
+		assert_eq!(a.union(&b), expected);
+		assert_eq!(a.union(&b), b.union(&a));
+		assert!(a.difference(&a).is_empty())
+	}
+
+	#[test]
+	fn from_array() {
+		let set: Multiset<&str> = Multiset::from(["a", "b", "a"]);
+		assert_eq!(set.len(), 3);
+		assert!(set.contains(&"a"));
+		assert!(set.contains(&"b"));
+		assert!(!set.contains(&"c"));
+
+		let expected: Multiset<&str> = vec!["a", "a", "b"].into();
+		assert_eq!(set, expected)
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let set: Multiset<String> = ["a", "b", "a"].into_iter().map(String::from).collect();
+		let json = json_syntax::to_value(&set).unwrap();
+		assert!(json.is_array());
+
+		let back: Multiset<String> = json_syntax::from_value(json).unwrap();
+		assert_eq!(back, set)
+	}
+}
@@ -12,10 +12,7 @@ pub type PropertyObjects<T, B> = Multiset<IndexedObject<T, B>>;
 
 /// Properties of a node object, and their associated objects.
 #[derive(Educe, Debug, Clone)]
-#[educe(
-	PartialEq(bound = "T: Eq + Hash, B: Eq + Hash"),
-	Eq(bound = "T: Eq + Hash, B: Eq + Hash")
-)]
+#[educe(Eq(bound = "T: Eq + Hash, B: Eq + Hash"))]
 pub struct Properties<T, B>(IndexMap<Id<T, B>, PropertyObjects<T, B>>);
 
 impl<T, B> Default for Properties<T, B> {
@@ -61,6 +58,12 @@ impl<T, B> Properties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the properties with the given comparison function on their
+	/// identifier.
+	pub fn sort_by(&mut self, mut compare: impl FnMut(&Id<T, B>, &Id<T, B>) -> std::cmp::Ordering) {
+		self.0.sort_by(|a, _, b, _| compare(a, b))
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> Properties<T, B> {
@@ -82,6 +85,25 @@ impl<T: Eq + Hash, B: Eq + Hash> Properties<T, B> {
 		}
 	}
 
+	/// Returns the set of objects associated to the given property, if any.
+	#[inline(always)]
+	pub fn get_objects<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&self,
+		prop: &Q,
+	) -> Option<&PropertyObjects<T, B>> {
+		self.0.get(prop)
+	}
+
+	/// Returns a mutable reference to the set of objects associated to the
+	/// given property, if any.
+	#[inline(always)]
+	pub fn get_objects_mut<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&mut self,
+		prop: &Q,
+	) -> Option<&mut PropertyObjects<T, B>> {
+		self.0.get_mut(prop)
+	}
+
 	/// Get one of the objects associated to the given property.
 	///
 	/// If multiple objects are found, there are no guaranties on which object will be returned.
@@ -227,6 +249,18 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Properties<T, B> {
 	}
 }
 
+impl<T: Eq + Hash, B: Eq + Hash> PartialEq for Properties<T, B> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.len() == other.0.len()
+			&& self.0.iter().all(|(prop, objects)| {
+				other
+					.0
+					.get(prop)
+					.is_some_and(|other_objects| objects.hashed_eq(other_objects))
+			})
+	}
+}
+
 impl<T: Hash, B: Hash> Hash for Properties<T, B> {
 	#[inline(always)]
 	fn hash<H: Hasher>(&self, h: &mut H) {
@@ -15,10 +15,7 @@ pub type ReversePropertyNodes<T = IriBuf, B = BlankIdBuf> = Multiset<IndexedNode
 
 /// Reverse properties of a node object, and their associated nodes.
 #[derive(Educe, Debug, Clone)]
-#[educe(
-	PartialEq(bound = "T: Eq + Hash, B: Eq + Hash"),
-	Eq(bound = "T: Eq + Hash, B: Eq + Hash")
-)]
+#[educe(Eq(bound = "T: Eq + Hash, B: Eq + Hash"))]
 pub struct ReverseProperties<T = IriBuf, B = BlankIdBuf>(
 	IndexMap<Id<T, B>, ReversePropertyNodes<T, B>>,
 );
@@ -66,6 +63,12 @@ impl<T, B> ReverseProperties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the reverse properties with the given comparison function on their
+	/// identifier.
+	pub fn sort_by(&mut self, mut compare: impl FnMut(&Id<T, B>, &Id<T, B>) -> std::cmp::Ordering) {
+		self.0.sort_by(|a, _, b, _| compare(a, b))
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> ReverseProperties<T, B> {
@@ -90,6 +93,26 @@ impl<T: Eq + Hash, B: Eq + Hash> ReverseProperties<T, B> {
 		}
 	}
 
+	/// Returns the set of nodes associated to the given reverse property, if
+	/// any.
+	#[inline(always)]
+	pub fn get_nodes<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&self,
+		prop: &Q,
+	) -> Option<&ReversePropertyNodes<T, B>> {
+		self.0.get(prop)
+	}
+
+	/// Returns a mutable reference to the set of nodes associated to the given
+	/// reverse property, if any.
+	#[inline(always)]
+	pub fn get_nodes_mut<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&mut self,
+		prop: &Q,
+	) -> Option<&mut ReversePropertyNodes<T, B>> {
+		self.0.get_mut(prop)
+	}
+
 	/// Get one of the nodes associated to the given reverse property.
 	///
 	/// If multiple nodes are found, there are no guaranties on which node will be returned.
@@ -232,6 +255,18 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for ReverseProperties<T
 	}
 }
 
+impl<T: Eq + Hash, B: Eq + Hash> PartialEq for ReverseProperties<T, B> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.len() == other.0.len()
+			&& self.0.iter().all(|(prop, nodes)| {
+				other
+					.0
+					.get(prop)
+					.is_some_and(|other_nodes| nodes.hashed_eq(other_nodes))
+			})
+	}
+}
+
 impl<T: Hash, B: Hash> Hash for ReverseProperties<T, B> {
 	#[inline(always)]
 	fn hash<H: Hasher>(&self, h: &mut H) {
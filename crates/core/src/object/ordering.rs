@@ -0,0 +1,262 @@
+//! Stable ordering of objects.
+//!
+//! Node properties are multisets, and expanded documents are sets, so the
+//! order in which objects are stored does not matter for equality. This
+//! module defines a total ordering over objects that can be used to sort them
+//! and obtain a deterministic order.
+use super::{Literal, Node, Object, Value};
+use crate::{Id, Indexed};
+use indexmap::IndexSet;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::hash::Hash;
+
+/// Compares two node identifiers.
+///
+/// IRIs come first, then blank node identifiers, then invalid identifiers.
+pub fn compare_ids<T: Ord, B: Ord>(a: &Id<T, B>, b: &Id<T, B>) -> Ordering {
+	fn rank<T, B>(id: &Id<T, B>) -> u8 {
+		match id {
+			Id::Valid(rdf_types::Id::Iri(_)) => 0,
+			Id::Valid(rdf_types::Id::Blank(_)) => 1,
+			Id::Invalid(_) => 2,
+		}
+	}
+
+	match (a, b) {
+		(Id::Valid(rdf_types::Id::Iri(a)), Id::Valid(rdf_types::Id::Iri(b))) => a.cmp(b),
+		(Id::Valid(rdf_types::Id::Blank(a)), Id::Valid(rdf_types::Id::Blank(b))) => a.cmp(b),
+		(Id::Invalid(a), Id::Invalid(b)) => a.cmp(b),
+		_ => rank(a).cmp(&rank(b)),
+	}
+}
+
+/// Compares two optional values, with `None` coming last.
+fn compare_options<T>(
+	a: Option<T>,
+	b: Option<T>,
+	compare: impl FnOnce(T, T) -> Ordering,
+) -> Ordering {
+	match (a, b) {
+		(Some(a), Some(b)) => compare(a, b),
+		(Some(_), None) => Ordering::Less,
+		(None, Some(_)) => Ordering::Greater,
+		(None, None) => Ordering::Equal,
+	}
+}
+
+/// Compares two sequences lexicographically.
+fn compare_sequences<T>(
+	a: impl IntoIterator<Item = T>,
+	b: impl IntoIterator<Item = T>,
+	mut compare: impl FnMut(T, T) -> Ordering,
+) -> Ordering {
+	let mut a = a.into_iter();
+	let mut b = b.into_iter();
+
+	loop {
+		match (a.next(), b.next()) {
+			(Some(a), Some(b)) => match compare(a, b) {
+				Ordering::Equal => (),
+				other => break other,
+			},
+			(Some(_), None) => break Ordering::Greater,
+			(None, Some(_)) => break Ordering::Less,
+			(None, None) => break Ordering::Equal,
+		}
+	}
+}
+
+/// Compares two indexed values, then their index.
+fn compare_indexed<T>(
+	a: &Indexed<T>,
+	b: &Indexed<T>,
+	compare: impl FnOnce(&T, &T) -> Ordering,
+) -> Ordering {
+	compare(a.inner(), b.inner()).then_with(|| a.index().cmp(&b.index()))
+}
+
+/// Sorts the given set of indexed values recursively.
+pub(crate) fn sort_set<T: Eq + Hash>(
+	set: &mut IndexSet<Indexed<T>>,
+	sort: impl Fn(&mut T),
+	compare: impl Fn(&T, &T) -> Ordering,
+) {
+	// Sorting an item changes its hash, so the set is rebuilt.
+	let mut items: Vec<_> = std::mem::take(set).into_iter().collect();
+
+	for item in &mut items {
+		sort(item)
+	}
+
+	items.sort_by(|a, b| compare_indexed(a, b, &compare));
+	set.extend(items)
+}
+
+impl<T> Value<T> {
+	/// Returns the lexical form of this value.
+	fn lexical_form(&self) -> Cow<'_, str> {
+		match self {
+			Self::Literal(Literal::Null, _) => Cow::Borrowed("null"),
+			Self::Literal(Literal::Boolean(true), _) => Cow::Borrowed("true"),
+			Self::Literal(Literal::Boolean(false), _) => Cow::Borrowed("false"),
+			Self::Literal(Literal::Number(n), _) => Cow::Borrowed(n.as_str()),
+			Self::Literal(Literal::String(s), _) => Cow::Borrowed(s.as_str()),
+			Self::LangString(s) => Cow::Borrowed(s.as_str()),
+			Self::Json(json) => Cow::Owned(json.to_string()),
+		}
+	}
+
+	/// Compares two values by lexical form, then datatype.
+	///
+	/// This is a total order: values with the same lexical form and datatype
+	/// are further compared by kind and language.
+	pub fn stable_cmp(&self, other: &Self) -> Ordering
+	where
+		T: Ord,
+	{
+		self.lexical_form()
+			.cmp(&other.lexical_form())
+			.then_with(|| self.literal_type().cmp(&other.literal_type()))
+			.then_with(|| self.cmp(other))
+	}
+}
+
+impl<T: Ord, B: Ord> Node<T, B> {
+	/// Compares two nodes.
+	///
+	/// Nodes are ordered by identifier, IRIs first, then blank node
+	/// identifiers, then nodes without identifier. Nodes with the same
+	/// identifier are compared by types, properties, reverse properties,
+	/// graph and included nodes, in their current order. Nodes should hence
+	/// be [sorted](Self::sort) before being compared.
+	pub fn stable_cmp(&self, other: &Self) -> Ordering {
+		compare_options(self.id.as_ref(), other.id.as_ref(), compare_ids)
+			.then_with(|| compare_sequences(self.types(), other.types(), compare_ids))
+			.then_with(|| {
+				compare_sequences(
+					self.properties(),
+					other.properties(),
+					|(a, a_objects), (b, b_objects)| {
+						compare_ids(a, b).then_with(|| {
+							compare_sequences(a_objects, b_objects, |a, b| {
+								compare_indexed(a, b, Object::stable_cmp)
+							})
+						})
+					},
+				)
+			})
+			.then_with(|| {
+				compare_options(
+					self.reverse_properties(),
+					other.reverse_properties(),
+					|a, b| {
+						compare_sequences(a, b, |(a, a_nodes), (b, b_nodes)| {
+							compare_ids(a, b).then_with(|| {
+								compare_sequences(a_nodes, b_nodes, |a, b| {
+									compare_indexed(a, b, Node::stable_cmp)
+								})
+							})
+						})
+					},
+				)
+			})
+			.then_with(|| {
+				compare_options(self.graph(), other.graph(), |a, b| {
+					compare_sequences(a, b, |a, b| compare_indexed(a, b, Object::stable_cmp))
+				})
+			})
+			.then_with(|| {
+				compare_options(self.included(), other.included(), |a, b| {
+					compare_sequences(a, b, |a, b| compare_indexed(a, b, Node::stable_cmp))
+				})
+			})
+	}
+
+	/// Recursively sorts the types, properties, reverse properties, graph and
+	/// included nodes of this node using [`Self::stable_cmp`] and
+	/// [`Object::stable_cmp`].
+	pub fn sort(&mut self)
+	where
+		T: Hash,
+		B: Hash,
+	{
+		if let Some(types) = &mut self.types {
+			types.sort_by(compare_ids)
+		}
+
+		if let Some(graph) = &mut self.graph {
+			sort_set(graph, Object::sort, Object::stable_cmp)
+		}
+
+		if let Some(included) = &mut self.included {
+			sort_set(included, Node::sort, Node::stable_cmp)
+		}
+
+		for (_, objects) in self.properties.iter_mut() {
+			for object in objects.iter_mut() {
+				object.sort()
+			}
+
+			objects.sort_by(|a, b| compare_indexed(a, b, Object::stable_cmp))
+		}
+		self.properties.sort_by(compare_ids);
+
+		if let Some(reverse_properties) = &mut self.reverse_properties {
+			for (_, nodes) in reverse_properties.iter_mut() {
+				for node in nodes.iter_mut() {
+					node.sort()
+				}
+
+				nodes.sort_by(|a, b| compare_indexed(a, b, Node::stable_cmp))
+			}
+			reverse_properties.sort_by(compare_ids);
+		}
+	}
+}
+
+impl<T: Ord, B: Ord> Object<T, B> {
+	/// Compares two objects.
+	///
+	/// Value objects come first, sorted with [`Value::stable_cmp`], then node
+	/// objects, sorted with [`Node::stable_cmp`], then lists, compared item by
+	/// item.
+	pub fn stable_cmp(&self, other: &Self) -> Ordering {
+		fn rank<T, B>(object: &Object<T, B>) -> u8 {
+			match object {
+				Object::Value(_) => 0,
+				Object::Node(_) => 1,
+				Object::List(_) => 2,
+			}
+		}
+
+		match (self, other) {
+			(Self::Value(a), Self::Value(b)) => a.stable_cmp(b),
+			(Self::Node(a), Self::Node(b)) => a.stable_cmp(b),
+			(Self::List(a), Self::List(b)) => compare_sequences(a.iter(), b.iter(), |a, b| {
+				compare_indexed(a, b, Self::stable_cmp)
+			}),
+			_ => rank(self).cmp(&rank(other)),
+		}
+	}
+
+	/// Recursively sorts the nodes of this object.
+	///
+	/// The order of list items is meaningful and left unchanged, only the
+	/// items themselves are sorted.
+	pub fn sort(&mut self)
+	where
+		T: Hash,
+		B: Hash,
+	{
+		match self {
+			Self::Value(_) => (),
+			Self::Node(node) => node.sort(),
+			Self::List(list) => {
+				for item in list.iter_mut() {
+					item.sort()
+				}
+			}
+		}
+	}
+}
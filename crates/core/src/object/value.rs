@@ -200,6 +200,15 @@ impl<T> Value<T> {
 		}
 	}
 
+	/// Returns the language tagged string, if this value is one.
+	#[inline(always)]
+	pub fn as_lang_string(&self) -> Option<&LangString> {
+		match self {
+			Value::LangString(s) => Some(s),
+			_ => None,
+		}
+	}
+
 	/// Return the type of the value if any.
 	///
 	/// This will return `Some(Type::Json)` for JSON literal values.
@@ -266,6 +275,9 @@ impl<T> Value<T> {
 			.remove_unique("@type")
 			.map_err(InvalidExpandedJson::duplicate_key)?
 		{
+			Some(_) if object.contains_key("@language") || object.contains_key("@direction") => {
+				Err(InvalidExpandedJson::UnexpectedEntry)
+			}
 			Some(type_entry) => match type_entry.value {
 				json_syntax::Value::String(ty) => match ty.as_str() {
 					"@json" => Ok(Self::Json(value_entry.value)),
@@ -305,6 +317,48 @@ impl<T> Value<T> {
 		}
 	}
 
+	/// JSON-LD value equivalence.
+	///
+	/// Two values are equivalent if they have the same `@type`, `@language`
+	/// and `@direction`, and equivalent `@value`s. It differs from `==` in
+	/// that numbers are compared by value after normalization, so `1` is
+	/// equivalent to `1.0` and `1E0`. As with `==`, language tags are compared
+	/// case-insensitively, as specified by BCP47. JSON literals are compared
+	/// with [`Self::json_equivalent`].
+	pub fn is_equivalent(&self, other: &Self) -> bool
+	where
+		T: PartialEq,
+	{
+		match (self, other) {
+			(Self::Literal(a, a_ty), Self::Literal(b, b_ty)) => {
+				a_ty == b_ty
+					&& match (a, b) {
+						(Literal::Number(a), Literal::Number(b)) => a.canonical() == b.canonical(),
+						(a, b) => a == b,
+					}
+			}
+			(Self::LangString(a), Self::LangString(b)) => {
+				a.as_str() == b.as_str()
+					&& a.language() == b.language()
+					&& a.direction() == b.direction()
+			}
+			(Self::Json(_), Self::Json(_)) => self.json_equivalent(other),
+			_ => false,
+		}
+	}
+
+	/// Checks that both values are equivalent JSON literals.
+	///
+	/// JSON objects are compared regardless of the order of their entries,
+	/// while arrays must have the same items in the same order. Returns
+	/// `false` if any of the values is not a JSON literal.
+	pub fn json_equivalent(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Json(a), Self::Json(b)) => json_syntax::UnorderedPartialEq::unordered_eq(a, b),
+			_ => false,
+		}
+	}
+
 	/// Puts this value object literal into canonical form using the given
 	/// `buffer`.
 	///
@@ -634,3 +688,83 @@ impl<T, N: IriVocabulary<Iri = T>> IntoJsonWithContext<N> for Value<T> {
 		obj.into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::LenientLangTagBuf;
+	use json_syntax::Parse;
+
+	fn values() -> [Value; 5] {
+		[
+			Value::null(),
+			Value::Literal(Literal::Boolean(true), None),
+			Value::Literal(Literal::Number(NumberBuf::from(42u32)), None),
+			Value::Literal(Literal::String("foo".into()), None),
+			Value::LangString(
+				LangString::new(
+					"bar".into(),
+					Some(LenientLangTagBuf::new("en".to_owned()).0),
+					None,
+				)
+				.unwrap(),
+			),
+		]
+	}
+
+	#[test]
+	fn accessors() {
+		let [null, boolean, number, string, lang_string] = values();
+
+		assert_eq!(
+			values().map(|v| v.as_bool()),
+			[None, Some(true), None, None, None]
+		);
+		assert_eq!(
+			values().map(|v| v.as_number().map(|n| n.as_str().to_owned())),
+			[None, None, Some("42".to_owned()), None, None]
+		);
+		assert_eq!(
+			values().map(|v| v.as_str().map(ToOwned::to_owned)),
+			[
+				None,
+				None,
+				None,
+				Some("foo".to_owned()),
+				Some("bar".to_owned())
+			]
+		);
+
+		for value in [&null, &boolean, &number, &string] {
+			assert!(value.as_lang_string().is_none())
+		}
+
+		let s = lang_string.as_lang_string().unwrap();
+		assert_eq!(s.as_str(), "bar");
+		assert_eq!(s.language().map(|l| l.as_str()), Some("en"));
+
+		let json = Value::<IriBuf>::Json(json_syntax::Value::Boolean(true));
+		assert_eq!(json.as_bool(), None);
+		assert_eq!(json.as_number(), None);
+		assert_eq!(json.as_str(), None);
+		assert!(json.as_lang_string().is_none())
+	}
+
+	fn json(json: &str) -> Value {
+		Value::Json(json_syntax::Value::parse_str(json).unwrap().0)
+	}
+
+	#[test]
+	fn json_equivalence() {
+		let a = json(r#"{"a":1,"b":[1,2]}"#);
+		let b = json(r#"{"b":[1,2],"a":1}"#);
+		assert_ne!(a, b);
+		assert!(a.json_equivalent(&b));
+		assert!(a.is_equivalent(&b));
+
+		let c = json(r#"{"a":1,"b":[2,1]}"#);
+		assert!(!a.json_equivalent(&c));
+		assert!(!json("[1,2]").json_equivalent(&json("[2,1]")));
+		assert!(!Value::<IriBuf>::null().json_equivalent(&json("null")))
+	}
+}
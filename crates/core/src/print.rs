@@ -68,6 +68,21 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> PrintWithContext<N> for object::
 	}
 }
 
+impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> PrintWithContext<N> for Indexed<Object<T, B>> {
+	fn contextual_fmt_with(
+		&self,
+		vocabulary: &N,
+		f: &mut std::fmt::Formatter,
+		options: &Options,
+		indent: usize,
+	) -> std::fmt::Result {
+		let mut sizes = Vec::new();
+		self.contextual_pre_compute_size(vocabulary, options, &mut sizes);
+		let mut index = 0;
+		self.contextual_fmt_with_size(vocabulary, f, options, indent, &sizes, &mut index)
+	}
+}
+
 impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> PrintWithSizeAndContext<N>
 	for ExpandedDocument<T, B>
 {
@@ -243,3 +243,37 @@ impl<T: Eq + Hash, B: Eq + Hash> RdfQuads<T, B> for NodeMap<T, B> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{test_utils::document, RdfQuads};
+
+	#[test]
+	fn named_graph_list() {
+		let document = document(
+			r#"[{
+				"@id": "http://example.org/g",
+				"@graph": [{
+					"@id": "http://example.org/s",
+					"http://example.org/p": [{ "@list": [{ "@value": "a" }, { "@value": "b" }] }]
+				}]
+			}]"#,
+		);
+
+		let mut generator = rdf_types::generator::Blank::new();
+		let quads: Vec<_> = document.rdf_quads(&mut generator, None).cloned().collect();
+
+		// One quad linking the subject to the list head, then `rdf:first` and
+		// `rdf:rest` for each item.
+		assert_eq!(quads.len(), 5);
+		for rdf_types::Quad(_, _, _, graph) in &quads {
+			assert_eq!(graph.as_ref().unwrap().as_str(), "http://example.org/g")
+		}
+
+		let first = quads
+			.iter()
+			.filter(|q| q.1.as_str() == "http://www.w3.org/1999/02/22-rdf-syntax-ns#first")
+			.count();
+		assert_eq!(first, 2)
+	}
+}
@@ -120,6 +120,14 @@ impl<R> SerResource<R> {
 			&& self.list.is_empty()
 	}
 
+	fn next_list_node(&self) -> Option<&R> {
+		if self.is_list_node() {
+			self.list.rest.iter().next()
+		} else {
+			None
+		}
+	}
+
 	fn is_list_node(&self) -> bool {
 		self.types.iter().all(|ty| ty.is_list())
 			&& self.properties.is_empty()
@@ -155,6 +163,46 @@ impl<R> SerGraph<R> {
 	{
 		self.resources.entry(id).or_default()
 	}
+
+	/// Finds a list node whose `rdf:rest` chain loops back onto itself, in
+	/// this graph or any nested graph.
+	///
+	/// Only anonymous list nodes are followed, since the other resources are
+	/// never folded into lists: a cycle going through them is just an
+	/// ordinary graph.
+	fn find_list_cycle(&self, is_anonymous: &impl Fn(&R) -> bool) -> Option<&R>
+	where
+		R: Ord,
+	{
+		let mut visited = BTreeSet::new();
+
+		for id in self.resources.keys() {
+			let mut path = BTreeSet::new();
+			let mut current = id;
+
+			while !visited.contains(current) {
+				if !path.insert(current) {
+					return Some(current);
+				}
+
+				match self
+					.get(current)
+					.filter(|_| is_anonymous(current))
+					.and_then(SerResource::next_list_node)
+				{
+					Some(next) => current = next,
+					None => break,
+				}
+			}
+
+			visited.extend(path);
+		}
+
+		self.resources
+			.values()
+			.filter_map(|r| r.graph.as_ref())
+			.find_map(|graph| graph.find_list_cycle(is_anonymous))
+	}
 }
 
 enum RdfProperty {
@@ -225,6 +273,9 @@ pub enum SerializationError {
 
 	#[error("invalid number value")]
 	Number(linked_data_next::ContextIris, String),
+
+	#[error("cyclic list")]
+	CyclicList(linked_data_next::ContextIris),
 }
 
 #[derive(Clone, Copy)]
@@ -232,6 +283,7 @@ pub struct RdfTerms<R> {
 	list: Option<R>,
 	first: Option<R>,
 	rest: Option<R>,
+	nil: Option<R>,
 }
 
 impl<I, B> ExpandedDocument<I, B> {
@@ -257,6 +309,7 @@ impl<I, B> ExpandedDocument<I, B> {
 			list: None,
 			first: None,
 			rest: None,
+			nil: None,
 		};
 
 		for quad in quads {
@@ -265,7 +318,6 @@ impl<I, B> ExpandedDocument<I, B> {
 
 			match rdf_property(vocabulary, interpretation, quad.1) {
 				Some(RdfProperty::Type) => {
-					rdf_terms.first = Some(quad.1);
 					let ty = rdf_type(vocabulary, interpretation, quad.2);
 
 					if ty.is_list() {
@@ -280,23 +332,23 @@ impl<I, B> ExpandedDocument<I, B> {
 				}
 				Some(RdfProperty::Rest) => {
 					rdf_terms.rest = Some(quad.1);
-					if nil.is_none() {
-						for i in interpretation.iris_of(quad.2) {
-							let iri = vocabulary.iri(i).unwrap();
-							if iri == RDF_NIL {
-								nil = Some(quad.2);
-							}
-						}
-					}
-
 					subject.list.rest.insert(quad.2);
-					graph.resource_mut(quad.2).list.reverse_rest.insert(quad.1);
+					graph.resource_mut(quad.2).list.reverse_rest.insert(quad.0);
 				}
 				None => {
 					subject.insert(quad.1, quad.2);
 				}
 			}
 
+			if nil.is_none() {
+				for i in interpretation.iris_of(quad.2) {
+					let iri = vocabulary.iri(i).unwrap();
+					if iri == RDF_NIL {
+						nil = Some(quad.2);
+					}
+				}
+			}
+
 			let object = graph.resource_mut(quad.2);
 			if quad.1 == quad.2 {
 				object.references = usize::MAX;
@@ -306,8 +358,17 @@ impl<I, B> ExpandedDocument<I, B> {
 			}
 		}
 
+		rdf_terms.nil = nil;
 		let mut graph = node_map.fold_into_default_graph();
 
+		if let Some(id) = graph.find_list_cycle(&|id| is_anonymous(interpretation, id)) {
+			return Err(SerializationError::CyclicList(
+				context
+					.with_subject(id)
+					.into_iris(vocabulary, interpretation),
+			));
+		}
+
 		let mut lists = Vec::new();
 		if let Some(nil_id) = nil {
 			if let Some(nil) = graph.get(&nil_id) {
@@ -320,10 +381,14 @@ impl<I, B> ExpandedDocument<I, B> {
 
 								loop {
 									let first = head.list.first.iter().next().copied().unwrap();
-									let parent_id =
-										head.list.reverse_rest.iter().next().copied().unwrap();
 									values.push(first);
 
+									let Some(parent_id) =
+										head.list.reverse_rest.iter().next().copied()
+									else {
+										break;
+									};
+
 									if is_anonymous(interpretation, parent_id) {
 										if let Some(parent) = graph.get(&parent_id) {
 											if parent.references == 1 && parent.is_list_node() {
@@ -539,30 +604,33 @@ where
 	O::IntoIter: ExactSizeIterator,
 {
 	let context = context.with_predicate(prop);
+	let nil_as_list = rdf_terms.first != Some(prop);
 	match id_of(interpretation, prop) {
 		Some(prop) => {
 			let mut values = values.into_iter();
 
 			while values.len() > 1 {
 				let value = values.next().unwrap();
-				let v = render_object_or_reference(
+				let v = render_property_object(
 					vocabulary,
 					interpretation,
 					rdf_terms,
 					graph,
 					value,
+					nil_as_list,
 					context,
 				)?;
 				node.insert(prop.clone(), v);
 			}
 
 			if let Some(value) = values.next() {
-				let v = render_object_or_reference(
+				let v = render_property_object(
 					vocabulary,
 					interpretation,
 					rdf_terms,
 					graph,
 					value,
+					nil_as_list,
 					context,
 				)?;
 				node.insert(prop, v);
@@ -574,6 +642,33 @@ where
 	}
 }
 
+/// Renders the object of a property.
+///
+/// If `nil_as_list` is set, an `rdf:nil` object is rendered as an empty list
+/// rather than a reference to `rdf:nil`.
+fn render_property_object<V, I>(
+	vocabulary: &V,
+	interpretation: &I,
+	rdf_terms: RdfTerms<&I::Resource>,
+	graph: &SerGraph<&I::Resource>,
+	id: &I::Resource,
+	nil_as_list: bool,
+	context: linked_data_next::Context<I>,
+) -> Result<IndexedObject<V::Iri, V::BlankId>, SerializationError>
+where
+	V: Vocabulary,
+	I: ReverseTermInterpretation<Iri = V::Iri, BlankId = V::BlankId, Literal = V::Literal>,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I::Resource: Ord,
+{
+	if nil_as_list && rdf_terms.nil == Some(id) {
+		Ok(Indexed::none(Object::List(List::new(Vec::new()))))
+	} else {
+		render_object_or_reference(vocabulary, interpretation, rdf_terms, graph, id, context)
+	}
+}
+
 fn render_object_or_reference<V, I>(
 	vocabulary: &V,
 	interpretation: &I,
@@ -739,3 +834,116 @@ where
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rdf_types::{BlankIdBuf, IriBuf};
+
+	fn blank(id: &str) -> Term {
+		Term::blank(BlankIdBuf::new(id.to_owned()).unwrap())
+	}
+
+	fn iri(iri: &Iri) -> Term {
+		Term::iri(iri.to_owned())
+	}
+
+	fn quad(s: Term, p: &Iri, o: Term) -> Quad<Term> {
+		Quad(s, iri(p), o, None)
+	}
+
+	#[test]
+	fn cyclic_list() {
+		let a = IriBuf::new("http://example.org/a".to_owned()).unwrap();
+		let b = IriBuf::new("http://example.org/b".to_owned()).unwrap();
+		let quads = [
+			quad(blank("_:x"), RDF_FIRST, iri(&a)),
+			quad(blank("_:x"), RDF_REST, blank("_:y")),
+			quad(blank("_:y"), RDF_FIRST, iri(&b)),
+			quad(blank("_:y"), RDF_REST, blank("_:x")),
+		];
+
+		let result: Result<ExpandedDocument, _> =
+			ExpandedDocument::from_interpreted_quads(&(), &(), quads.iter().map(Quad::as_ref));
+
+		assert!(matches!(result, Err(SerializationError::CyclicList(_))))
+	}
+
+	#[test]
+	fn cyclic_rest_between_iri_nodes() {
+		let a = IriBuf::new("http://example.org/a".to_owned()).unwrap();
+		let b = IriBuf::new("http://example.org/b".to_owned()).unwrap();
+		let p = IriBuf::new("http://example.org/p".to_owned()).unwrap();
+		let quads = [
+			quad(iri(&a), RDF_REST, iri(&b)),
+			quad(iri(&a), p.as_iri(), iri(&b)),
+			quad(iri(&b), RDF_FIRST, iri(&a)),
+			quad(iri(&b), RDF_REST, iri(&a)),
+		];
+
+		let document: ExpandedDocument =
+			ExpandedDocument::from_interpreted_quads(&(), &(), quads.iter().map(Quad::as_ref))
+				.unwrap();
+
+		assert_eq!(document.len(), 2)
+	}
+
+	fn list_of(document: &ExpandedDocument, subject: &IriBuf, property: &IriBuf) -> Vec<Object> {
+		let node = document
+			.objects()
+			.iter()
+			.find_map(|o| {
+				o.as_node()
+					.filter(|n| n.id.as_ref().and_then(Id::as_iri) == Some(subject))
+			})
+			.expect("subject not found");
+
+		let value = node
+			.get_any(&Id::iri(property.clone()))
+			.expect("property not found");
+
+		value
+			.as_list()
+			.expect("expected a list")
+			.iter()
+			.map(|item| item.inner().clone())
+			.collect()
+	}
+
+	#[test]
+	fn nil_object_as_empty_list() {
+		let s = IriBuf::new("http://example.org/s".to_owned()).unwrap();
+		let p = IriBuf::new("http://example.org/p".to_owned()).unwrap();
+		let quads = [quad(iri(&s), &p, iri(RDF_NIL))];
+
+		let document: ExpandedDocument =
+			ExpandedDocument::from_interpreted_quads(&(), &(), quads.iter().map(Quad::as_ref))
+				.unwrap();
+
+		assert!(list_of(&document, &s, &p).is_empty())
+	}
+
+	#[test]
+	fn nil_terminated_list() {
+		let s = IriBuf::new("http://example.org/s".to_owned()).unwrap();
+		let p = IriBuf::new("http://example.org/p".to_owned()).unwrap();
+		let a = IriBuf::new("http://example.org/a".to_owned()).unwrap();
+		let b = IriBuf::new("http://example.org/b".to_owned()).unwrap();
+		let quads = [
+			quad(iri(&s), &p, blank("_:x")),
+			quad(blank("_:x"), RDF_FIRST, iri(&a)),
+			quad(blank("_:x"), RDF_REST, blank("_:y")),
+			quad(blank("_:y"), RDF_FIRST, iri(&b)),
+			quad(blank("_:y"), RDF_REST, iri(RDF_NIL)),
+		];
+
+		let document: ExpandedDocument =
+			ExpandedDocument::from_interpreted_quads(&(), &(), quads.iter().map(Quad::as_ref))
+				.unwrap();
+
+		let items = list_of(&document, &s, &p);
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[0].as_node().unwrap().id, Some(Id::iri(a)));
+		assert_eq!(items[1].as_node().unwrap().id, Some(Id::iri(b)))
+	}
+}
@@ -213,7 +213,9 @@ where
 			}
 
 			let entries: Cow<[Entry]> = if options.ordered {
-				Cow::Owned(element.entries().to_vec())
+				let mut entries = element.entries().to_vec();
+				entries.sort_by(|a, b| a.key.cmp(&b.key));
+				Cow::Owned(entries)
 			} else {
 				Cow::Borrowed(element.entries())
 			};
@@ -503,3 +505,334 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::test_utils::expanded;
+	use crate::{Error, Expand, ExpansionResult, Options};
+	use iref::IriBuf;
+	use json_ld_core_next::{ExpandedDocument, NoLoader};
+	use json_syntax::Parse;
+	use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+
+	async fn try_expand(json: &str) -> ExpansionResult<IriBuf, BlankIdBuf> {
+		let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+		json.expand_full(
+			no_vocabulary_mut(),
+			json_ld_core_next::Context::new(None),
+			None,
+			&NoLoader,
+			Options::default(),
+			(),
+		)
+		.await
+	}
+
+	async fn expand(json: &str) -> ExpandedDocument {
+		try_expand(json).await.unwrap()
+	}
+
+	#[async_std::test]
+	async fn type_scoped_context() {
+		let document = expand(
+			r#"{
+				"@context": {
+					"@vocab": "http://example.org/",
+					"Person": {
+						"@id": "http://schema.org/Person",
+						"@context": { "name": "http://schema.org/name" }
+					}
+				},
+				"@type": "Person",
+				"name": "Alice",
+				"knows": { "name": "Bob" }
+			}"#,
+		)
+		.await;
+
+		// Type-scoped contexts do not propagate to nested nodes by default.
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@type": ["http://schema.org/Person"],
+					"http://schema.org/name": [{ "@value": "Alice" }],
+					"http://example.org/knows": [{
+						"http://example.org/name": [{ "@value": "Bob" }]
+					}]
+				}]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn multiple_type_scoped_contexts() {
+		// Contexts are applied following the lexicographical order of the
+		// types, so `B` overrides the aliases of `A`.
+		let document = expand(
+			r#"{
+				"@context": {
+					"@vocab": "http://example.org/",
+					"B": { "@context": { "label": "http://example.org/b#label" } },
+					"A": {
+						"@context": {
+							"label": "http://example.org/a#label",
+							"title": "http://example.org/a#title"
+						}
+					}
+				},
+				"@type": ["B", "A"],
+				"label": "x",
+				"title": "y"
+			}"#,
+		)
+		.await;
+
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@type": ["http://example.org/B", "http://example.org/A"],
+					"http://example.org/b#label": [{ "@value": "x" }],
+					"http://example.org/a#title": [{ "@value": "y" }]
+				}]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn property_scoped_context() {
+		let context = |propagate: &str| {
+			format!(
+				r#"{{
+					"@context": {{
+						"@vocab": "http://example.org/",
+						"author": {{
+							"@context": {{
+								"@propagate": {propagate},
+								"name": "http://schema.org/name"
+							}}
+						}}
+					}},
+					"name": "Document",
+					"author": {{
+						"name": "Alice",
+						"knows": {{ "name": "Bob" }}
+					}}
+				}}"#
+			)
+		};
+
+		// The scoped context propagates to nested nodes by default.
+		assert_eq!(
+			expand(&context("true")).await,
+			expanded(
+				r#"[{
+					"http://example.org/name": [{ "@value": "Document" }],
+					"http://example.org/author": [{
+						"http://schema.org/name": [{ "@value": "Alice" }],
+						"http://example.org/knows": [{
+							"http://schema.org/name": [{ "@value": "Bob" }]
+						}]
+					}]
+				}]"#
+			)
+		);
+
+		// Only the immediate values are affected without propagation.
+		assert_eq!(
+			expand(&context("false")).await,
+			expanded(
+				r#"[{
+					"http://example.org/name": [{ "@value": "Document" }],
+					"http://example.org/author": [{
+						"http://schema.org/name": [{ "@value": "Alice" }],
+						"http://example.org/knows": [{
+							"http://example.org/name": [{ "@value": "Bob" }]
+						}]
+					}]
+				}]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn non_propagating_context() {
+		assert_eq!(
+			expand(
+				r#"{
+					"@context": { "@vocab": "http://example.org/" },
+					"child": {
+						"@context": {
+							"@propagate": false,
+							"self": "@id",
+							"kind": "@type",
+							"name": "http://schema.org/name"
+						},
+						"self": "http://example.org/c",
+						"kind": "Thing",
+						"name": "C",
+						"knows": { "kind": "Other", "name": "D" }
+					}
+				}"#
+			)
+			.await,
+			expanded(
+				r#"[{
+					"http://example.org/child": [{
+						"@id": "http://example.org/c",
+						"@type": ["http://example.org/Thing"],
+						"http://schema.org/name": [{ "@value": "C" }],
+						"http://example.org/knows": [{
+							"http://example.org/kind": [{ "@value": "Other" }],
+							"http://example.org/name": [{ "@value": "D" }]
+						}]
+					}]
+				}]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn included() {
+		let document = expand(
+			r#"{
+				"@context": { "@vocab": "http://example.org/" },
+				"@id": "http://example.org/a",
+				"@included": [
+					{ "@id": "http://example.org/b", "name": "B" },
+					{
+						"@id": "http://example.org/c",
+						"@included": { "@id": "http://example.org/d" }
+					}
+				]
+			}"#,
+		)
+		.await;
+
+		let node = document.main_node().unwrap();
+		let included = node.included().unwrap();
+		assert_eq!(included.len(), 2);
+
+		let ids: Vec<_> = included
+			.iter()
+			.map(|node| node.id.as_ref().unwrap().as_str())
+			.collect();
+		assert_eq!(ids, ["http://example.org/b", "http://example.org/c"]);
+
+		let nested = included.iter().find_map(|node| node.included()).unwrap();
+		assert_eq!(
+			nested.iter().next().unwrap().id.as_ref().unwrap().as_str(),
+			"http://example.org/d"
+		)
+	}
+
+	#[async_std::test]
+	async fn included_value() {
+		assert!(matches!(
+			try_expand(r#"{ "@included": [{ "@value": "not a node" }] }"#).await,
+			Err(Error::InvalidIncludedValue)
+		))
+	}
+
+	/// Characterization test: JSON-LD-star annotations are not implemented
+	/// yet, and `@annotation` is an unknown keyword-like entry, ignored on
+	/// node objects and invalid on value objects.
+	///
+	/// This records the current behavior only. Supporting annotations is
+	/// still an open request, and must update this test.
+	#[async_std::test]
+	async fn annotation_characterization() {
+		let document = expand(
+			r#"{
+				"@id": "http://example.org/a",
+				"http://example.org/p": {
+					"@id": "http://example.org/b",
+					"@annotation": { "http://example.org/certainty": 0.8 }
+				}
+			}"#,
+		)
+		.await;
+
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@id": "http://example.org/a",
+					"http://example.org/p": [{ "@id": "http://example.org/b" }]
+				}]"#
+			)
+		);
+
+		assert!(matches!(
+			try_expand(
+				r#"{
+					"http://example.org/p": {
+						"@value": "v",
+						"@annotation": { "http://example.org/certainty": 0.8 }
+					}
+				}"#
+			)
+			.await,
+			Err(Error::Value(_))
+		))
+	}
+
+	#[async_std::test]
+	async fn keyword_aliases() {
+		let document = expand(
+			r#"{
+				"@context": {
+					"@vocab": "http://example.org/",
+					"id": "@id",
+					"type": "@type"
+				},
+				"id": "http://example.org/a",
+				"type": "Person",
+				"name": "Alice"
+			}"#,
+		)
+		.await;
+
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/Person"],
+					"http://example.org/name": [{ "@value": "Alice" }]
+				}]"#
+			)
+		)
+	}
+
+	/// An alias is processed as its keyword, hence collides with it.
+	#[async_std::test]
+	async fn keyword_alias_collisions() {
+		assert!(matches!(
+			try_expand(
+				r#"{
+					"@context": { "id": "@id" },
+					"id": "http://example.org/a",
+					"@id": "http://example.org/b"
+				}"#
+			)
+			.await,
+			Err(Error::CollidingKeywords)
+		));
+
+		// Since JSON-LD 1.1, `@type` values are merged instead.
+		let document = expand(
+			r#"{
+				"@context": { "@vocab": "http://example.org/", "type": "@type" },
+				"@id": "http://example.org/a",
+				"type": "Person",
+				"@type": "Agent"
+			}"#,
+		)
+		.await;
+
+		let types = document.iter().next().unwrap().as_node().unwrap().types();
+		assert_eq!(types.len(), 2)
+	}
+}
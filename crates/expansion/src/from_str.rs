@@ -0,0 +1,125 @@
+use json_ld_core_next::{ExpandedDocument, Loader};
+use json_syntax::{Parse, Value};
+
+use crate::{Error, Expand};
+
+/// Error raised by [`FromJsonStr::from_json_str`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromJsonStrError {
+	/// The input is not valid JSON.
+	#[error("Invalid JSON: {0}")]
+	Parse(#[from] json_syntax::parse::Error),
+
+	/// The input could not be expanded.
+	#[error(transparent)]
+	Expansion(#[from] Error),
+}
+
+/// Expansion of JSON-LD text.
+///
+/// This trait is implemented by [`ExpandedDocument`], and wires the JSON
+/// parser and the expansion algorithm together.
+///
+/// # Example
+///
+/// ```
+/// use json_ld_core_next::{ExpandedDocument, NoLoader};
+/// use json_ld_expansion_next::FromJsonStr;
+///
+/// # #[async_std::test]
+/// # async fn example() {
+/// let document = ExpandedDocument::from_json_str(
+///   r#"{
+///     "@context": { "name": "http://xmlns.com/foaf/0.1/name" },
+///     "name": "Alice"
+///   }"#,
+///   &NoLoader,
+/// )
+/// .await
+/// .unwrap();
+///
+/// assert_eq!(document.len(), 1);
+/// # }
+/// ```
+pub trait FromJsonStr: Sized {
+	/// Parses and expands the given JSON-LD document.
+	///
+	/// The embedded `@context` is processed from an empty initial context,
+	/// without base URL. Remote contexts are fetched with the given `loader`.
+	#[allow(async_fn_in_trait)]
+	async fn from_json_str<L: Loader>(input: &str, loader: &L) -> Result<Self, FromJsonStrError>;
+}
+
+impl FromJsonStr for ExpandedDocument {
+	async fn from_json_str<L: Loader>(input: &str, loader: &L) -> Result<Self, FromJsonStrError> {
+		let (json, _) = Value::parse_str(input)?;
+		Ok(json.expand(loader).await?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::expanded;
+	use json_ld_core_next::NoLoader;
+
+	#[async_std::test]
+	async fn inline_context() {
+		let document = ExpandedDocument::from_json_str(
+			r#"{
+				"@context": { "@vocab": "http://example.org/", "knows": { "@type": "@id" } },
+				"@id": "http://example.org/a",
+				"name": "Alice",
+				"knows": "http://example.org/b"
+			}"#,
+			&NoLoader,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(
+			document,
+			expanded(
+				r#"[{
+					"@id": "http://example.org/a",
+					"http://example.org/name": [{ "@value": "Alice" }],
+					"http://example.org/knows": [{ "@id": "http://example.org/b" }]
+				}]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn top_level_graph() {
+		let document = ExpandedDocument::from_json_str(
+			r#"{
+				"@context": { "@vocab": "http://example.org/" },
+				"@graph": [
+					{ "@id": "http://example.org/a", "name": "Alice" },
+					{ "@id": "http://example.org/b", "name": "Bob" }
+				]
+			}"#,
+			&NoLoader,
+		)
+		.await
+		.unwrap();
+
+		assert_eq!(
+			document,
+			expanded(
+				r#"[
+					{ "@id": "http://example.org/a", "http://example.org/name": [{ "@value": "Alice" }] },
+					{ "@id": "http://example.org/b", "http://example.org/name": [{ "@value": "Bob" }] }
+				]"#
+			)
+		)
+	}
+
+	#[async_std::test]
+	async fn invalid_json() {
+		let error = ExpandedDocument::from_json_str("{", &NoLoader)
+			.await
+			.unwrap_err();
+		assert!(matches!(error, FromJsonStrError::Parse(_)))
+	}
+}
@@ -16,15 +16,21 @@ mod document;
 mod element;
 mod error;
 mod expanded;
+mod from_str;
 mod literal;
 mod node;
 mod options;
+mod stream;
+#[cfg(test)]
+mod test_utils;
 mod value;
 mod warning;
 
 pub use error::*;
 pub use expanded::*;
+pub use from_str::*;
 pub use options::*;
+pub use stream::*;
 pub use warning::*;
 
 pub(crate) use array::*;
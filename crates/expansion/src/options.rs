@@ -72,3 +72,76 @@ impl Default for Policy {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Expand, ExpansionResult};
+	use iref::IriBuf;
+	use json_ld_core_next::{Context, NoLoader};
+	use json_ld_syntax_next::ErrorCode;
+	use json_syntax::Parse;
+	use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+
+	async fn expand(json: &str, options: Options) -> ExpansionResult<IriBuf, BlankIdBuf> {
+		let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+		json.expand_full(
+			no_vocabulary_mut(),
+			Context::new(None),
+			None,
+			&NoLoader,
+			options,
+			(),
+		)
+		.await
+	}
+
+	/// Returns the properties of the single node of the given document.
+	async fn properties(json: &str, options: Options) -> Vec<String> {
+		let document = expand(json, options).await.unwrap();
+		let node = document.iter().next().unwrap().as_node().unwrap();
+		node.properties()
+			.iter()
+			.map(|(prop, _)| prop.as_str().to_owned())
+			.collect()
+	}
+
+	#[async_std::test]
+	async fn ordered() {
+		let options = Options {
+			ordered: true,
+			..Default::default()
+		};
+
+		let a = properties(
+			r#"{ "http://example.org/b": "1", "http://example.org/a": "2" }"#,
+			options,
+		)
+		.await;
+		let b = properties(
+			r#"{ "http://example.org/a": "2", "http://example.org/b": "1" }"#,
+			options,
+		)
+		.await;
+
+		assert_eq!(a, ["http://example.org/a", "http://example.org/b"]);
+		assert_eq!(a, b)
+	}
+
+	#[async_std::test]
+	async fn json_ld_1_0_rejects_json_type() {
+		let json = r#"{
+			"@context": { "data": { "@id": "http://example.org/data", "@type": "@json" } },
+			"data": { "key": "value" }
+		}"#;
+
+		assert!(expand(json, Options::default()).await.is_ok());
+
+		let options = Options {
+			processing_mode: ProcessingMode::JsonLd1_0,
+			..Default::default()
+		};
+		let error = expand(json, options).await.unwrap_err();
+		assert_eq!(error.code(), ErrorCode::InvalidTypeMapping)
+	}
+}
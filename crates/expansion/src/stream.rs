@@ -0,0 +1,158 @@
+use crate::{
+	expand_element, filter_top_level_item, ActiveProperty, Error, Loader, Options, WarningHandler,
+};
+use futures::Stream;
+use json_ld_core_next::{Context, Environment, IndexedObject};
+use json_syntax::Value;
+use rdf_types::VocabularyMut;
+use std::hash::Hash;
+
+struct ExpandStreamState<'a, N: VocabularyMut, L, W> {
+	vocabulary: &'a mut N,
+	loader: &'a L,
+	warnings: W,
+	active_context: Context<N::Iri, N::BlankId>,
+	base_url: Option<&'a N::Iri>,
+	options: Options,
+	items: std::slice::Iter<'a, Value>,
+	unwrap_graph: bool,
+	pending: std::vec::IntoIter<IndexedObject<N::Iri, N::BlankId>>,
+}
+
+/// Expand the given JSON-LD document, one top-level item at a time.
+///
+/// When `document` is an array, each of its elements is expanded only when
+/// the returned stream is polled, and the resulting top-level objects are
+/// yielded before moving on to the next element. The expanded document is
+/// never materialized: at any given time only the objects produced by the
+/// current element are held in memory. As a consequence, and unlike
+/// [`Expand`](crate::Expand), duplicate top-level objects are not merged.
+///
+/// The `active_context` is the initial context shared by every element.
+/// After an error is yielded, the stream ends.
+pub fn expand_stream<'a, N, L, W>(
+	vocabulary: &'a mut N,
+	document: &'a Value,
+	active_context: Context<N::Iri, N::BlankId>,
+	base_url: Option<&'a N::Iri>,
+	loader: &'a L,
+	options: Options,
+	warnings: W,
+) -> impl 'a + Stream<Item = Result<IndexedObject<N::Iri, N::BlankId>, Error>>
+where
+	N: VocabularyMut,
+	N::Iri: Clone + Eq + Hash,
+	N::BlankId: Clone + Eq + Hash,
+	L: Loader,
+	W: 'a + WarningHandler<N>,
+{
+	let (items, unwrap_graph) = match document {
+		Value::Array(items) => (items.iter(), false),
+		other => (std::slice::from_ref(other).iter(), true),
+	};
+
+	let state = ExpandStreamState {
+		vocabulary,
+		loader,
+		warnings,
+		active_context,
+		base_url,
+		options,
+		items,
+		unwrap_graph,
+		pending: Vec::new().into_iter(),
+	};
+
+	futures::stream::unfold(state, |mut state| async move {
+		loop {
+			for object in state.pending.by_ref() {
+				if filter_top_level_item(&object) {
+					return Some((Ok(object), state));
+				}
+			}
+
+			let element = state.items.next()?;
+			let expanded = expand_element(
+				Environment {
+					vocabulary: &mut *state.vocabulary,
+					loader: state.loader,
+					warnings: &mut state.warnings,
+				},
+				&state.active_context,
+				ActiveProperty::None,
+				element,
+				state.base_url,
+				state.options,
+				false,
+			)
+			.await;
+
+			match expanded {
+				Ok(expanded) => {
+					let mut objects: Vec<_> = expanded.into_iter().collect();
+
+					if state.unwrap_graph && objects.len() == 1 {
+						match objects.pop().unwrap().into_unnamed_graph() {
+							Ok(graph) => objects.extend(graph),
+							Err(object) => objects.push(object),
+						}
+					}
+
+					state.pending = objects.into_iter()
+				}
+				Err(e) => {
+					state.items = [].iter();
+					return Some((Err(e), state));
+				}
+			}
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::StreamExt;
+	use json_ld_core_next::{NoLoader, Object};
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+
+	#[async_std::test]
+	async fn items_arrive_one_at_a_time() {
+		let (json, _) = Value::parse_str(
+			r#"[
+				{ "@id": "http://example.org/a", "http://example.org/p": "a" },
+				{ "@id": "http://example.org/b", "http://example.org/p": "b" },
+				{ "@id": "http://example.org/c", "@type": 1 }
+			]"#,
+		)
+		.unwrap();
+
+		let stream = expand_stream(
+			no_vocabulary_mut(),
+			&json,
+			Context::new(None),
+			None,
+			&NoLoader,
+			Options::default(),
+			(),
+		);
+		futures::pin_mut!(stream);
+
+		for expected in ["http://example.org/a", "http://example.org/b"] {
+			let object = stream.next().await.unwrap().unwrap();
+			match object.inner() {
+				Object::Node(node) => {
+					assert_eq!(node.id.as_ref().unwrap().as_str(), expected)
+				}
+				_ => panic!("expected a node object"),
+			}
+		}
+
+		assert!(matches!(
+			stream.next().await,
+			Some(Err(Error::InvalidTypeValue))
+		));
+		assert!(stream.next().await.is_none())
+	}
+}
@@ -0,0 +1,9 @@
+use json_ld_core_next::{ExpandedDocument, TryFromJson};
+use json_syntax::Parse;
+use rdf_types::vocabulary::no_vocabulary_mut;
+
+/// Parses the given expanded JSON-LD document.
+pub fn expanded(json: &str) -> ExpandedDocument {
+	let (json, _) = json_syntax::Value::parse_str(json).unwrap();
+	ExpandedDocument::try_from_json_in(no_vocabulary_mut(), json).unwrap()
+}
@@ -0,0 +1,491 @@
+//! URDNA2015 RDF dataset canonicalization.
+//!
+//! See <https://www.w3.org/TR/rdf-canon/>.
+use std::collections::{BTreeMap, HashMap};
+
+use indexmap::{IndexMap, IndexSet};
+use json_ld_core_next::{
+	rdf::{RdfDirection, XSD_STRING},
+	ExpandedDocument, RdfQuads,
+};
+use rdf_types::{BlankIdBuf, Id, LexicalQuad, Literal, LiteralType, Quad, Term};
+use sha2::{Digest, Sha256};
+
+/// Canonicalize the RDF dataset of the given expanded JSON-LD document into
+/// sorted N-Quads.
+///
+/// The dataset is produced the same way as [`to_nquads`](crate::to_nquads),
+/// then canonicalized with [`canonical_nquads`].
+pub fn to_canonical_nquads(document: &ExpandedDocument) -> String {
+	let mut generator = rdf_types::generator::Blank::new();
+	let quads: Vec<_> = document
+		.rdf_quads(&mut generator, Some(RdfDirection::I18nDatatype))
+		.cloned()
+		.filter_map(|Quad(s, p, o, g)| match p {
+			Id::Iri(p) => Some(Quad(s, p, o, g)),
+			Id::Blank(_) => None,
+		})
+		.collect();
+
+	canonical_nquads(quads)
+}
+
+/// Canonicalize the given RDF dataset and serialize it into sorted N-Quads.
+pub fn canonical_nquads(quads: impl IntoIterator<Item = LexicalQuad>) -> String {
+	canonicalize(quads)
+		.iter()
+		.map(|quad| nquad(quad, &mut |b| b.to_string()))
+		.collect()
+}
+
+/// Canonicalize the given RDF dataset using the URDNA2015 algorithm.
+///
+/// Blank nodes are relabeled `_:c14n0`, `_:c14n1`, ... and the resulting
+/// quads are returned in canonical N-Quads order. Duplicate quads are
+/// removed.
+pub fn canonicalize(quads: impl IntoIterator<Item = LexicalQuad>) -> Vec<LexicalQuad> {
+	let quads: IndexSet<LexicalQuad> = quads.into_iter().collect();
+	let canonical = Canonicalizer::new(&quads).issue_identifiers();
+
+	let mut result: Vec<_> = quads
+		.iter()
+		.map(|quad| relabel_quad(quad, &canonical))
+		.map(|quad| (nquad(&quad, &mut |b| b.to_string()), quad))
+		.collect();
+
+	result.sort_by(|a, b| a.0.cmp(&b.0));
+	result.dedup_by(|a, b| a.0 == b.0);
+	result.into_iter().map(|(_, quad)| quad).collect()
+}
+
+/// Blank node identifier issuer.
+#[derive(Clone)]
+struct IdentifierIssuer {
+	prefix: &'static str,
+	issued: IndexMap<BlankIdBuf, BlankIdBuf>,
+}
+
+impl IdentifierIssuer {
+	fn new(prefix: &'static str) -> Self {
+		Self {
+			prefix,
+			issued: IndexMap::new(),
+		}
+	}
+
+	fn get(&self, blank_id: &BlankIdBuf) -> Option<&BlankIdBuf> {
+		self.issued.get(blank_id)
+	}
+
+	fn issue(&mut self, blank_id: &BlankIdBuf) -> BlankIdBuf {
+		if let Some(issued) = self.issued.get(blank_id) {
+			return issued.clone();
+		}
+
+		let issued = BlankIdBuf::new(format!("_:{}{}", self.prefix, self.issued.len())).unwrap();
+		self.issued.insert(blank_id.clone(), issued.clone());
+		issued
+	}
+}
+
+struct Canonicalizer<'a> {
+	blank_node_quads: IndexMap<&'a BlankIdBuf, IndexSet<&'a LexicalQuad>>,
+	first_degree_hashes: HashMap<&'a BlankIdBuf, String>,
+	canonical: IdentifierIssuer,
+}
+
+impl<'a> Canonicalizer<'a> {
+	fn new(quads: &'a IndexSet<LexicalQuad>) -> Self {
+		let mut blank_node_quads: IndexMap<_, IndexSet<_>> = IndexMap::new();
+		for quad in quads {
+			for (blank_id, _) in blank_components(quad) {
+				blank_node_quads.entry(blank_id).or_default().insert(quad);
+			}
+		}
+
+		let first_degree_hashes = blank_node_quads
+			.iter()
+			.map(|(&blank_id, quads)| (blank_id, first_degree_hash(blank_id, quads)))
+			.collect();
+
+		Self {
+			blank_node_quads,
+			first_degree_hashes,
+			canonical: IdentifierIssuer::new("c14n"),
+		}
+	}
+
+	fn issue_identifiers(mut self) -> IdentifierIssuer {
+		let mut hash_to_blank_ids: BTreeMap<&str, Vec<&BlankIdBuf>> = BTreeMap::new();
+		for &blank_id in self.blank_node_quads.keys() {
+			hash_to_blank_ids
+				.entry(&self.first_degree_hashes[blank_id])
+				.or_default()
+				.push(blank_id)
+		}
+
+		// Blank nodes with a unique first degree hash.
+		for blank_ids in hash_to_blank_ids.values() {
+			if let [blank_id] = blank_ids.as_slice() {
+				self.canonical.issue(blank_id);
+			}
+		}
+
+		// Blank nodes sharing their first degree hash.
+		for blank_ids in hash_to_blank_ids.values().filter(|ids| ids.len() > 1) {
+			let mut hash_path_list = Vec::new();
+
+			for &blank_id in blank_ids {
+				if self.canonical.get(blank_id).is_some() {
+					continue;
+				}
+
+				let mut issuer = IdentifierIssuer::new("b");
+				issuer.issue(blank_id);
+				hash_path_list.push(self.hash_n_degree_quads(blank_id, &issuer));
+			}
+
+			hash_path_list.sort_by(|a, b| a.0.cmp(&b.0));
+			for (_, issuer) in hash_path_list {
+				for blank_id in issuer.issued.keys() {
+					self.canonical.issue(blank_id);
+				}
+			}
+		}
+
+		self.canonical
+	}
+
+	fn hash_related_blank_node(
+		&self,
+		related: &BlankIdBuf,
+		quad: &LexicalQuad,
+		issuer: &IdentifierIssuer,
+		position: char,
+	) -> String {
+		let identifier = match self.canonical.get(related).or_else(|| issuer.get(related)) {
+			Some(issued) => issued.as_str(),
+			None => &self.first_degree_hashes[related],
+		};
+
+		let mut input = position.to_string();
+		if position != 'g' {
+			input.push('<');
+			input.push_str(quad.1.as_str());
+			input.push('>');
+		}
+		input.push_str(identifier);
+
+		sha256(&input)
+	}
+
+	fn hash_n_degree_quads(
+		&self,
+		identifier: &BlankIdBuf,
+		issuer: &IdentifierIssuer,
+	) -> (String, IdentifierIssuer) {
+		let mut related_blank_ids: BTreeMap<String, Vec<&BlankIdBuf>> = BTreeMap::new();
+		for &quad in &self.blank_node_quads[identifier] {
+			for (related, position) in blank_components(quad) {
+				if related != identifier {
+					let hash = self.hash_related_blank_node(related, quad, issuer, position);
+					related_blank_ids.entry(hash).or_default().push(related)
+				}
+			}
+		}
+
+		let mut issuer = issuer.clone();
+		let mut data_to_hash = String::new();
+
+		for (related_hash, blank_ids) in related_blank_ids {
+			data_to_hash.push_str(&related_hash);
+
+			let mut chosen: Option<(String, IdentifierIssuer)> = None;
+
+			'permutations: for permutation in permutations(&blank_ids) {
+				let mut issuer_copy = issuer.clone();
+				let mut path = String::new();
+				let mut recursion_list = Vec::new();
+
+				for related in permutation.iter().copied() {
+					match self.canonical.get(related) {
+						Some(canonical) => path.push_str(canonical.as_str()),
+						None => {
+							if issuer_copy.get(related).is_none() {
+								recursion_list.push(related)
+							}
+
+							path.push_str(issuer_copy.issue(related).as_str())
+						}
+					}
+
+					if is_longer_path(&path, chosen.as_ref()) {
+						continue 'permutations;
+					}
+				}
+
+				for related in recursion_list {
+					let (hash, result_issuer) = self.hash_n_degree_quads(related, &issuer_copy);
+					path.push_str(issuer_copy.issue(related).as_str());
+					path.push('<');
+					path.push_str(&hash);
+					path.push('>');
+					issuer_copy = result_issuer;
+
+					if is_longer_path(&path, chosen.as_ref()) {
+						continue 'permutations;
+					}
+				}
+
+				match &chosen {
+					Some((chosen_path, _)) if *chosen_path <= path => (),
+					_ => chosen = Some((path, issuer_copy)),
+				}
+			}
+
+			let (chosen_path, chosen_issuer) = chosen.unwrap();
+			data_to_hash.push_str(&chosen_path);
+			issuer = chosen_issuer
+		}
+
+		(sha256(&data_to_hash), issuer)
+	}
+}
+
+/// Checks if the given `path` can be skipped because it already compares
+/// greater than the chosen path.
+fn is_longer_path(path: &str, chosen: Option<&(String, IdentifierIssuer)>) -> bool {
+	chosen.is_some_and(|(chosen_path, _)| {
+		path.len() >= chosen_path.len() && path > chosen_path.as_str()
+	})
+}
+
+/// Returns every blank node of the given quad (subject, object and graph)
+/// along with its position.
+fn blank_components(quad: &LexicalQuad) -> impl Iterator<Item = (&BlankIdBuf, char)> {
+	let subject = match &quad.0 {
+		Id::Blank(b) => Some((b, 's')),
+		Id::Iri(_) => None,
+	};
+
+	let object = match &quad.2 {
+		Term::Id(Id::Blank(b)) => Some((b, 'o')),
+		_ => None,
+	};
+
+	let graph = match &quad.3 {
+		Some(Id::Blank(b)) => Some((b, 'g')),
+		_ => None,
+	};
+
+	subject.into_iter().chain(object).chain(graph)
+}
+
+fn first_degree_hash(blank_id: &BlankIdBuf, quads: &IndexSet<&LexicalQuad>) -> String {
+	let mut lines: Vec<_> = quads
+		.iter()
+		.map(|quad| {
+			nquad(quad, &mut |b| {
+				if b == blank_id {
+					"_:a".to_owned()
+				} else {
+					"_:z".to_owned()
+				}
+			})
+		})
+		.collect();
+
+	lines.sort();
+	sha256(&lines.concat())
+}
+
+fn sha256(input: &str) -> String {
+	format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// Returns every permutation of the given items.
+fn permutations<T: Copy>(items: &[T]) -> Vec<Vec<T>> {
+	if items.len() <= 1 {
+		return vec![items.to_vec()];
+	}
+
+	let mut result = Vec::new();
+	for i in 0..items.len() {
+		let mut rest = items.to_vec();
+		let item = rest.remove(i);
+		for mut permutation in permutations(&rest) {
+			permutation.insert(0, item);
+			result.push(permutation)
+		}
+	}
+
+	result
+}
+
+fn relabel_quad(quad: &LexicalQuad, canonical: &IdentifierIssuer) -> LexicalQuad {
+	let relabel = |id: &Id| match id {
+		Id::Blank(b) => Id::Blank(canonical.get(b).unwrap().clone()),
+		Id::Iri(i) => Id::Iri(i.clone()),
+	};
+
+	Quad(
+		relabel(&quad.0),
+		quad.1.clone(),
+		match &quad.2 {
+			Term::Id(id) => Term::Id(relabel(id)),
+			Term::Literal(l) => Term::Literal(l.clone()),
+		},
+		quad.3.as_ref().map(relabel),
+	)
+}
+
+/// Serializes the given quad into a canonical N-Quads line, using `blank` to
+/// label blank nodes.
+fn nquad(quad: &LexicalQuad, blank: &mut impl FnMut(&BlankIdBuf) -> String) -> String {
+	let mut line = String::new();
+
+	write_id(&mut line, &quad.0, blank);
+	line.push_str(" <");
+	line.push_str(quad.1.as_str());
+	line.push_str("> ");
+
+	match &quad.2 {
+		Term::Id(id) => write_id(&mut line, id, blank),
+		Term::Literal(literal) => write_literal(&mut line, literal),
+	}
+
+	if let Some(graph) = &quad.3 {
+		line.push(' ');
+		write_id(&mut line, graph, blank)
+	}
+
+	line.push_str(" .\n");
+	line
+}
+
+fn write_id(out: &mut String, id: &Id, blank: &mut impl FnMut(&BlankIdBuf) -> String) {
+	match id {
+		Id::Iri(iri) => {
+			out.push('<');
+			out.push_str(iri.as_str());
+			out.push('>')
+		}
+		Id::Blank(b) => out.push_str(&blank(b)),
+	}
+}
+
+fn write_literal(out: &mut String, literal: &Literal) {
+	out.push('"');
+	for c in literal.value.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+
+	match &literal.type_ {
+		LiteralType::Any(ty) if ty.as_iri() == XSD_STRING => (),
+		LiteralType::Any(ty) => {
+			out.push_str("^^<");
+			out.push_str(ty.as_str());
+			out.push('>')
+		}
+		LiteralType::LangString(tag) => {
+			out.push('@');
+			out.push_str(tag.as_str())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use iref::IriBuf;
+
+	fn iri(s: &str) -> IriBuf {
+		IriBuf::new(s.to_owned()).unwrap()
+	}
+
+	fn id(s: &str) -> Id {
+		match s.strip_prefix("_:") {
+			Some(_) => Id::Blank(BlankIdBuf::new(s.to_owned()).unwrap()),
+			None => Id::Iri(iri(s)),
+		}
+	}
+
+	fn quad(s: &str, p: &str, o: &str) -> LexicalQuad {
+		Quad(id(s), iri(p), Term::Id(id(o)), None)
+	}
+
+	#[test]
+	fn unique_hashes() {
+		let quads = [
+			quad("http://example.com/#p", "http://example.com/#q", "_:e0"),
+			quad("http://example.com/#p", "http://example.com/#r", "_:e1"),
+			quad("_:e0", "http://example.com/#s", "http://example.com/#u"),
+			quad("_:e1", "http://example.com/#t", "http://example.com/#u"),
+		];
+
+		let set: IndexSet<_> = quads.iter().cloned().collect();
+		let canonicalizer = Canonicalizer::new(&set);
+		let e0 = BlankIdBuf::new("_:e0".to_owned()).unwrap();
+		let e1 = BlankIdBuf::new("_:e1".to_owned()).unwrap();
+		assert_eq!(
+			canonicalizer.first_degree_hashes[&e0],
+			"21d1dd5ba21f3dee9d76c0c00c260fa6f5d5d65315099e553026f4828d0dc77a"
+		);
+		assert_eq!(
+			canonicalizer.first_degree_hashes[&e1],
+			"6fa0b9bdb376852b5743ff39ca4cbf7ea14d34966b2828478fbf222e7c764473"
+		);
+
+		assert_eq!(
+			canonical_nquads(quads),
+			"<http://example.com/#p> <http://example.com/#q> _:c14n0 .\n\
+			<http://example.com/#p> <http://example.com/#r> _:c14n1 .\n\
+			_:c14n0 <http://example.com/#s> <http://example.com/#u> .\n\
+			_:c14n1 <http://example.com/#t> <http://example.com/#u> .\n"
+		)
+	}
+
+	#[test]
+	fn shared_hashes() {
+		let next = "http://example.org/vocab#next";
+		let prev = "http://example.org/vocab#prev";
+		let quads = [
+			quad("_:e0", next, "_:e1"),
+			quad("_:e0", prev, "_:e2"),
+			quad("_:e1", next, "_:e2"),
+			quad("_:e1", prev, "_:e0"),
+			quad("_:e2", next, "_:e0"),
+			quad("_:e2", prev, "_:e1"),
+		];
+
+		let expected = "_:c14n0 <http://example.org/vocab#next> _:c14n2 .\n\
+			_:c14n0 <http://example.org/vocab#prev> _:c14n1 .\n\
+			_:c14n1 <http://example.org/vocab#next> _:c14n0 .\n\
+			_:c14n1 <http://example.org/vocab#prev> _:c14n2 .\n\
+			_:c14n2 <http://example.org/vocab#next> _:c14n1 .\n\
+			_:c14n2 <http://example.org/vocab#prev> _:c14n0 .\n";
+
+		assert_eq!(canonical_nquads(quads.clone()), expected);
+
+		// Independent of the input order and labels.
+		let relabeled = quads.into_iter().rev().map(|Quad(s, p, o, g)| {
+			let rename = |term: Id| match term {
+				Id::Blank(b) => id(&b.as_str().replace("_:e", "_:x")),
+				other => other,
+			};
+			let o = match o {
+				Term::Id(o) => Term::Id(rename(o)),
+				other => other,
+			};
+			Quad(rename(s), p, o, g)
+		});
+		assert_eq!(canonical_nquads(relabeled), expected)
+	}
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use iref::{Iri, IriBuf};
+use json_ld_core_next::Value;
+
+/// Error returned by a fallible datatype converter.
+pub type ConverterError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Datatype converter.
+type Converter<T> = Box<dyn Fn(&str) -> Result<Value<T>, ConverterError>>;
+
+/// Custom datatype converters.
+///
+/// Maps a datatype IRI to a function turning the lexical form of a literal
+/// of this datatype into a JSON-LD value. The serializer consults the
+/// registry before falling back to a typed string value.
+pub struct DatatypeRegistry<T = IriBuf> {
+	converters: HashMap<IriBuf, Converter<T>>,
+}
+
+impl<T> Default for DatatypeRegistry<T> {
+	fn default() -> Self {
+		Self {
+			converters: HashMap::new(),
+		}
+	}
+}
+
+impl<T> DatatypeRegistry<T> {
+	/// Creates a new empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of registered datatypes.
+	pub fn len(&self) -> usize {
+		self.converters.len()
+	}
+
+	/// Checks if no datatype is registered.
+	pub fn is_empty(&self) -> bool {
+		self.converters.is_empty()
+	}
+
+	/// Registers the converter for the given datatype, replacing the previous
+	/// one, if any.
+	pub fn insert(&mut self, datatype: IriBuf, converter: impl 'static + Fn(&str) -> Value<T>) {
+		self.converters
+			.insert(datatype, Box::new(move |lexical| Ok(converter(lexical))));
+	}
+
+	/// Registers a fallible converter for the given datatype, replacing the
+	/// previous one, if any.
+	///
+	/// Serializing a literal the converter fails on fails with
+	/// [`Error::CustomConverter`](crate::Error::CustomConverter).
+	pub fn insert_fallible<E: Into<ConverterError>>(
+		&mut self,
+		datatype: IriBuf,
+		converter: impl 'static + Fn(&str) -> Result<Value<T>, E>,
+	) {
+		self.converters.insert(
+			datatype,
+			Box::new(move |lexical| converter(lexical).map_err(Into::into)),
+		);
+	}
+
+	/// Checks if a converter is registered for the given datatype.
+	pub fn contains(&self, datatype: &Iri) -> bool {
+		self.converters.contains_key(datatype)
+	}
+
+	/// Converts the given lexical form using the converter registered for
+	/// `datatype`, if any.
+	///
+	/// Returns `None` if the converter fails. Use [`Self::try_convert`] to
+	/// get the error.
+	pub fn convert(&self, datatype: &Iri, lexical: &str) -> Option<Value<T>> {
+		self.try_convert(datatype, lexical)?.ok()
+	}
+
+	/// Converts the given lexical form using the converter registered for
+	/// `datatype`, if any, returning the converter error on failure.
+	pub fn try_convert(
+		&self,
+		datatype: &Iri,
+		lexical: &str,
+	) -> Option<Result<Value<T>, ConverterError>> {
+		self.converters.get(datatype).map(|f| f(lexical))
+	}
+}
+
+impl<T> std::fmt::Debug for DatatypeRegistry<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_set().entries(self.converters.keys()).finish()
+	}
+}
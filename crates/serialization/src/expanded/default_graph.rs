@@ -1,5 +1,6 @@
 use std::hash::Hash;
 
+use iref::Iri;
 use json_ld_core_next::{ExpandedDocument, Indexed, Object};
 use linked_data_next::{CowRdfTerm, LinkedDataResource};
 use rdf_types::{
@@ -10,13 +11,15 @@ use rdf_types::{
 	Interpretation, Term, Vocabulary,
 };
 
-use crate::Error;
+use crate::{Error, Options, RelativeIdPolicy};
 
 use super::{node::SerializeNode, value::literal_to_value};
 
 pub struct SerializeDefaultGraph<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
+	base: Option<&'a Iri>,
 	result: &'a mut ExpandedDocument<V::Iri, V::BlankId>,
 }
 
@@ -24,14 +27,58 @@ impl<'a, I, V: Vocabulary> SerializeDefaultGraph<'a, I, V> {
 	pub fn new(
 		vocabulary: &'a mut V,
 		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+		base: Option<&'a Iri>,
 		result: &'a mut ExpandedDocument<V::Iri, V::BlankId>,
 	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
+			base,
 			result,
 		}
 	}
+
+	/// Returns the `@id` of the node identified by `id`.
+	///
+	/// If a base IRI is set and `id` is an IRI under it, the IRI is made
+	/// relative to the base. Since a relative reference is not an IRI, it is
+	/// stored as an invalid identifier, or rejected, according to
+	/// [`Options::relative_ids`].
+	fn node_id(
+		&self,
+		id: rdf_types::Id<V::Iri, V::BlankId>,
+	) -> Result<json_ld_core_next::Id<V::Iri, V::BlankId>, Error> {
+		if let (Some(base), rdf_types::Id::Iri(i)) = (self.base, &id) {
+			if let Some(relative) = self
+				.vocabulary
+				.iri(i)
+				.and_then(|iri| relative_to(iri, base))
+			{
+				return match self.options.relative_ids {
+					RelativeIdPolicy::Keep => Ok(json_ld_core_next::Id::Invalid(relative)),
+					RelativeIdPolicy::Reject => Err(Error::InvalidNodeId { value: relative }),
+				};
+			}
+		}
+
+		Ok(json_ld_core_next::Id::Valid(id))
+	}
+}
+
+/// Makes `iri` relative to `base`, unless it is not under it.
+fn relative_to(iri: &Iri, base: &Iri) -> Option<String> {
+	let relative = iri.relative_to(base);
+
+	if relative.scheme().is_some()
+		|| relative.as_str().starts_with('/')
+		|| relative.as_str().starts_with("..")
+	{
+		None
+	} else {
+		Some(relative.as_str().to_owned())
+	}
 }
 
 impl<I: Interpretation, V: Vocabulary> linked_data_next::GraphVisitor<I, V>
@@ -56,15 +103,15 @@ where
 			.map(CowRdfTerm::into_owned)
 		{
 			Some(Term::Literal(lit)) => {
-				let value = literal_to_value(self.vocabulary, lit);
+				let value = literal_to_value(self.vocabulary, lit, self.options)?;
 				self.result.insert(Indexed::new(Object::Value(value), None));
 				return Ok(());
 			}
-			Some(Term::Id(id)) => Some(json_ld_core_next::Id::Valid(id)),
-			_ => None,
+			Some(Term::Id(id)) => Some(self.node_id(id)?),
+			None => None,
 		};
 
-		let serializer = SerializeNode::new(self.vocabulary, self.interpretation, id);
+		let serializer = SerializeNode::new(self.vocabulary, self.interpretation, self.options, id);
 
 		let node = value.visit_subject(serializer)?;
 		self.result.insert(Indexed::new(Object::node(node), None));
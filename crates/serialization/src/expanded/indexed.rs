@@ -0,0 +1,124 @@
+use std::hash::Hash;
+
+use json_ld_core_next::{Indexed, IndexedObject};
+use linked_data_next::{LinkedDataResource, LinkedDataSubject};
+use rdf_types::{
+	interpretation::{
+		ReverseBlankIdInterpretation, ReverseIriInterpretation, ReverseLiteralInterpretation,
+	},
+	vocabulary::IriVocabularyMut,
+	Vocabulary,
+};
+
+use crate::{Error, Options};
+
+use super::serialize_object_with_options;
+
+/// Resource providing an `@index` hint.
+///
+/// RDF has no notion of index, hence the dataset visitors always produce
+/// unindexed objects. Types implementing this trait can be serialized with
+/// [`serialize_indexed_object_with`] to keep their index.
+pub trait IndexedResource {
+	/// Returns the index of this resource, if any.
+	fn index(&self) -> Option<&str>;
+}
+
+impl<T: ?Sized + IndexedResource> IndexedResource for &T {
+	fn index(&self) -> Option<&str> {
+		T::index(*self)
+	}
+}
+
+/// Serialize the given Linked-Data value into an indexed JSON-LD object using
+/// a custom vocabulary and interpretation.
+pub fn serialize_indexed_object_with<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	value: &T,
+) -> Result<IndexedObject<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + IndexedResource + LinkedDataResource<I, V> + LinkedDataSubject<I, V>,
+{
+	serialize_indexed_object_with_options(vocabulary, interpretation, Options::default(), value)
+}
+
+/// Serialize the given Linked-Data value into an indexed JSON-LD object using
+/// a custom vocabulary, interpretation and serialization options.
+pub fn serialize_indexed_object_with_options<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	value: &T,
+) -> Result<IndexedObject<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + IndexedResource + LinkedDataResource<I, V> + LinkedDataSubject<I, V>,
+{
+	let object = serialize_object_with_options(vocabulary, interpretation, options, value)?;
+	Ok(Indexed::new(object, value.index().map(ToOwned::to_owned)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use iref::IriBuf;
+	use json_ld_core_next::ExpandedDocument;
+	use linked_data_next::{ResourceInterpretation, SubjectVisitor};
+
+	struct Entry {
+		id: IriBuf,
+		index: &'static str,
+	}
+
+	impl IndexedResource for Entry {
+		fn index(&self) -> Option<&str> {
+			Some(self.index)
+		}
+	}
+
+	impl LinkedDataResource for Entry {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			self.id.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl LinkedDataSubject for Entry {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(&self, visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn index_survives() {
+		let entry = Entry {
+			id: IriBuf::new("http://example.org/a".to_owned()).unwrap(),
+			index: "first",
+		};
+
+		let mut document = ExpandedDocument::new();
+		document.insert(serialize_indexed_object_with(&mut (), &mut (), &entry).unwrap());
+
+		let object = document.iter().next().unwrap();
+		assert_eq!(object.index(), Some("first"));
+		assert_eq!(
+			object.as_node().unwrap().id.as_ref().unwrap().as_str(),
+			"http://example.org/a"
+		)
+	}
+}
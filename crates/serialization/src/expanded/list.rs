@@ -13,22 +13,28 @@ use rdf_types::{
 	Id, Interpretation, Term, Vocabulary,
 };
 
-use crate::Error;
+use crate::{Error, Options, TermPosition};
 
-use super::object::serialize_object_with;
+use super::{node::predicate_error, object::serialize_object_with_options};
 
 pub struct SerializeList<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	first: Option<Object<V::Iri, V::BlankId>>,
 	rest: Vec<IndexedObject<V::Iri, V::BlankId>>,
 }
 
 impl<'a, I, V: Vocabulary> SerializeList<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			first: None,
 			rest: Vec::new(),
 		}
@@ -56,26 +62,34 @@ where
 		let repr = predicate
 			.interpretation(self.vocabulary, self.interpretation)
 			.into_lexical_representation(self.vocabulary, self.interpretation)
-			.map(CowRdfTerm::into_term);
+			.map(CowRdfTerm::into_owned);
 
 		match repr {
 			Some(Term::Id(id)) => {
 				if let Id::Iri(iri) = id {
-					let iri = self.vocabulary.iri(iri.as_ref()).unwrap();
+					let iri = self.vocabulary.iri(&iri).ok_or(Error::UnresolvableIri {
+						position: TermPosition::Predicate,
+					})?;
 					if iri == RDF_FIRST {
-						let serializer =
-							SerializeListFirst::new(self.vocabulary, self.interpretation);
+						let serializer = SerializeListFirst::new(
+							self.vocabulary,
+							self.interpretation,
+							self.options,
+						);
 						self.first = value.visit_objects(serializer)?;
 					} else if iri == RDF_REST {
-						let serializer =
-							SerializeListRest::new(self.vocabulary, self.interpretation);
+						let serializer = SerializeListRest::new(
+							self.vocabulary,
+							self.interpretation,
+							self.options,
+						);
 						self.rest = value.visit_objects(serializer)?;
 					}
 				}
 
 				Ok(())
 			}
-			_ => Err(Error::InvalidPredicate),
+			other => Err(predicate_error(other)),
 		}
 	}
 
@@ -112,14 +126,20 @@ where
 pub struct SerializeListFirst<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	result: Option<Object<V::Iri, V::BlankId>>,
 }
 
 impl<'a, I, V: Vocabulary> SerializeListFirst<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			result: None,
 		}
 	}
@@ -142,9 +162,10 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		self.result = Some(serialize_object_with(
+		self.result = Some(serialize_object_with_options(
 			self.vocabulary,
 			self.interpretation,
+			self.options,
 			value,
 		)?);
 		Ok(())
@@ -158,14 +179,20 @@ where
 pub struct SerializeListRest<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	result: Vec<IndexedObject<V::Iri, V::BlankId>>,
 }
 
 impl<'a, I, V: Vocabulary> SerializeListRest<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			result: Vec::new(),
 		}
 	}
@@ -188,7 +215,7 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		let serializer = SerializeList::new(self.vocabulary, self.interpretation);
+		let serializer = SerializeList::new(self.vocabulary, self.interpretation, self.options);
 		self.result = value.visit_subject(serializer)?;
 		Ok(())
 	}
@@ -1,18 +1,20 @@
-use json_ld_core_next::{ExpandedDocument, Indexed, Node, Object};
+use iref::Iri;
+use json_ld_core_next::{object::Graph, ExpandedDocument, Indexed, Node, Object};
 use linked_data_next::CowRdfTerm;
 use rdf_types::{
 	interpretation::{
 		ReverseBlankIdInterpretation, ReverseIriInterpretation, ReverseLiteralInterpretation,
 	},
-	vocabulary::IriVocabularyMut,
-	Interpretation, Term, Vocabulary,
+	vocabulary::{BlankIdVocabulary, BlankIdVocabularyMut, IriVocabularyMut},
+	BlankIdBuf, Interpretation, Term, Vocabulary,
 };
 use std::hash::Hash;
 
-use crate::Error;
+use crate::{BlankIdIssuer, Error, Options, Warnings};
 
 mod default_graph;
 mod graph;
+mod indexed;
 mod list;
 mod node;
 mod object;
@@ -22,25 +24,405 @@ mod value;
 use default_graph::SerializeDefaultGraph;
 use graph::SerializeGraph;
 
-pub use node::serialize_node_with;
-pub use object::serialize_object_with;
+pub use indexed::{
+	serialize_indexed_object_with, serialize_indexed_object_with_options, IndexedResource,
+};
+pub use node::{serialize_node_with, serialize_node_with_options};
+pub use object::{serialize_object_with, serialize_object_with_options};
+pub(crate) use value::literal_to_value;
+
+/// Serialize the given Linked-Data value into an expanded JSON-LD document.
+///
+/// ```
+/// use json_ld_serialization_next::serialize_expanded;
+///
+/// #[derive(linked_data_next::Serialize)]
+/// #[ld(prefix("ex" = "http://example.org/"))]
+/// struct Person {
+///     #[ld(id)]
+///     id: iref::IriBuf,
+///
+///     #[ld("ex:name")]
+///     name: String,
+/// }
+///
+/// let person = Person {
+///     id: iref::IriBuf::new("http://example.org/john".to_owned()).unwrap(),
+///     name: "John Smith".to_owned(),
+/// };
+///
+/// let document = serialize_expanded(&person).unwrap();
+/// let node = document.iter().next().unwrap().as_node().unwrap();
+/// assert_eq!(node.id.as_ref().unwrap().as_str(), "http://example.org/john");
+/// ```
+pub fn serialize_expanded<T>(value: &T) -> Result<ExpandedDocument, Error>
+where
+	T: ?Sized + linked_data_next::LinkedData,
+{
+	serialize_expanded_with(&mut (), &mut (), value)
+}
+
+/// Serialize the given Linked-Data value into an expanded JSON-LD document
+/// using a custom vocabulary and interpretation.
+pub fn serialize_expanded_with<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	value: &T,
+) -> Result<ExpandedDocument<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut + BlankIdVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: Interpretation
+		+ ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + linked_data_next::LinkedData<I, V>,
+{
+	serialize_expanded_with_options(vocabulary, interpretation, Options::default(), value)
+}
+
+/// Serialize the given Linked-Data value into an expanded JSON-LD document
+/// using a custom vocabulary, interpretation and serialization options.
+pub fn serialize_expanded_with_options<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	value: &T,
+) -> Result<ExpandedDocument<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut + BlankIdVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: Interpretation
+		+ ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + linked_data_next::LinkedData<I, V>,
+{
+	serialize_expanded_with_base(vocabulary, interpretation, options, None, value)
+}
+
+/// Serialize the given Linked-Data value into an expanded JSON-LD document
+/// with node identifiers relative to the given `base` IRI.
+///
+/// Only the `@id` of the default graph nodes are made relative, and only when
+/// they are under `base`. Other IRIs are left unchanged. Relative identifiers
+/// are handled according to [`Options::relative_ids`].
+pub fn serialize_expanded_with_base<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	base: Option<&Iri>,
+	value: &T,
+) -> Result<ExpandedDocument<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut + BlankIdVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: Interpretation
+		+ ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + linked_data_next::LinkedData<I, V>,
+{
+	let serializer = SerializeExpandedDocumentBuilder::new()
+		.with_options(options)
+		.with_base(base)
+		.build(vocabulary, interpretation)
+		.with_fresh_blank_ids();
+
+	value.visit(serializer)
+}
+
+/// Serialize the given Linked-Data value into an expanded JSON-LD document,
+/// drawing the identifiers of anonymous graphs and nodes from `issuer`.
+///
+/// Serializing several values with the same issuer ensures that their blank
+/// node identifiers do not collide.
+pub fn serialize_expanded_with_issuer<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	issuer: &mut BlankIdIssuer,
+	value: &T,
+) -> Result<ExpandedDocument<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut + BlankIdVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: Interpretation
+		+ ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + linked_data_next::LinkedData<I, V>,
+{
+	let serializer = SerializeExpandedDocumentBuilder::new()
+		.with_options(options)
+		.build(vocabulary, interpretation)
+		.with_issuer(issuer);
 
+	value.visit(serializer)
+}
+
+/// Blank node identifier issuer used by a serializer.
+enum Issuer<'a> {
+	/// Issuer owned by the serializer.
+	Owned(BlankIdIssuer),
+
+	/// Issuer shared with other serializers.
+	Shared(&'a mut BlankIdIssuer),
+}
+
+impl Issuer<'_> {
+	fn get(&mut self) -> &mut BlankIdIssuer {
+		match self {
+			Self::Owned(issuer) => issuer,
+			Self::Shared(issuer) => issuer,
+		}
+	}
+}
+
+/// Expanded document serializer.
+///
+/// Visits a Linked-Data dataset and builds the equivalent expanded JSON-LD
+/// document.
 pub struct SerializeExpandedDocument<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
+	base: Option<&'a Iri>,
+	issuer: Issuer<'a>,
+	insert_blank_id: Option<InsertBlankId<V>>,
 	result: ExpandedDocument<V::Iri, V::BlankId>,
 }
 
+/// Inserts a fresh blank node identifier into the vocabulary.
+///
+/// Only available when the vocabulary implements [`BlankIdVocabularyMut`],
+/// which is not required by the serializer itself.
+pub(crate) type InsertBlankId<V> = fn(&mut V, BlankIdBuf) -> <V as BlankIdVocabulary>::BlankId;
+
+fn insert_blank_id<V: BlankIdVocabularyMut>(vocabulary: &mut V, id: BlankIdBuf) -> V::BlankId {
+	vocabulary.insert_owned_blank_id(id)
+}
+
 impl<'a, I, V: Vocabulary> SerializeExpandedDocument<'a, I, V> {
+	/// Creates a serializer with the default options.
+	///
+	/// See [`SerializeExpandedDocumentBuilder`] to set up the options of the
+	/// serializer.
 	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+		SerializeExpandedDocumentBuilder::new().build(vocabulary, interpretation)
+	}
+
+	/// Sets the base IRI the default graph node identifiers are made relative
+	/// to.
+	pub fn with_base(self, base: Option<&'a Iri>) -> Self {
+		Self { base, ..self }
+	}
+
+	/// Sets the issuer of the anonymous graphs and nodes identifiers.
+	///
+	/// By default, the serializer uses its own fresh issuer. This also
+	/// enables [`with_fresh_blank_ids`](Self::with_fresh_blank_ids).
+	pub fn with_issuer(self, issuer: &'a mut BlankIdIssuer) -> Self
+	where
+		V: BlankIdVocabularyMut,
+	{
 		Self {
+			issuer: Issuer::Shared(issuer),
+			..self
+		}
+		.with_fresh_blank_ids()
+	}
+
+	/// Names anonymous graphs with fresh blank node identifiers drawn from
+	/// the issuer.
+	///
+	/// Without it, anonymous graphs are serialized as graph objects without
+	/// `@id`.
+	pub fn with_fresh_blank_ids(self) -> Self
+	where
+		V: BlankIdVocabularyMut,
+	{
+		Self {
+			insert_blank_id: Some(insert_blank_id::<V>),
+			..self
+		}
+	}
+
+	/// Sets the collector of the serialization warnings.
+	///
+	/// This overrides the [`Options::warnings`] collector. By default,
+	/// warnings are ignored.
+	pub fn with_warnings(mut self, warnings: &'a Warnings) -> Self {
+		self.options.warnings = Some(warnings);
+		self
+	}
+}
+
+/// Expanded document serializer builder.
+///
+/// ```
+/// use json_ld_serialization_next::SerializeExpandedDocumentBuilder;
+///
+/// let base = iref::Iri::new("http://example.org/").unwrap();
+/// let (mut vocabulary, mut interpretation) = ((), ());
+/// let serializer = SerializeExpandedDocumentBuilder::new()
+///     .with_base(Some(base))
+///     .with_canonical_json(true)
+///     .build(&mut vocabulary, &mut interpretation);
+/// # let _ = serializer;
+/// ```
+pub struct SerializeExpandedDocumentBuilder<'a, T = iref::IriBuf> {
+	options: Options<'a, T>,
+	base: Option<&'a Iri>,
+	issuer: Option<&'a mut BlankIdIssuer>,
+}
+
+impl<T> Default for SerializeExpandedDocumentBuilder<'_, T> {
+	fn default() -> Self {
+		Self {
+			options: Options::default(),
+			base: None,
+			issuer: None,
+		}
+	}
+}
+
+impl<'a, T> SerializeExpandedDocumentBuilder<'a, T> {
+	/// Creates a builder with the default options.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the serialization options, replacing the ones set so far.
+	pub fn with_options(self, options: Options<'a, T>) -> Self {
+		Self { options, ..self }
+	}
+
+	/// Sets the base IRI the default graph node identifiers are made relative
+	/// to.
+	pub fn with_base(self, base: Option<&'a Iri>) -> Self {
+		Self { base, ..self }
+	}
+
+	/// Sets the issuer of the anonymous graphs and nodes identifiers.
+	///
+	/// By default, the serializer uses its own fresh issuer. Identifiers are
+	/// only issued once
+	/// [`with_fresh_blank_ids`](SerializeExpandedDocument::with_fresh_blank_ids)
+	/// is enabled on the built serializer.
+	pub fn with_issuer(self, issuer: &'a mut BlankIdIssuer) -> Self {
+		Self {
+			issuer: Some(issuer),
+			..self
+		}
+	}
+
+	/// Sets the collector of the serialization warnings.
+	pub fn with_warnings(mut self, warnings: &'a Warnings) -> Self {
+		self.options.warnings = Some(warnings);
+		self
+	}
+
+	/// Sets the [`Options::canonical_json`] option.
+	pub fn with_canonical_json(mut self, canonical_json: bool) -> Self {
+		self.options.canonical_json = canonical_json;
+		self
+	}
+
+	/// Builds the serializer.
+	pub fn build<I, V: Vocabulary<Iri = T>>(
+		self,
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+	) -> SerializeExpandedDocument<'a, I, V> {
+		SerializeExpandedDocument {
 			vocabulary,
 			interpretation,
+			options: self.options,
+			base: self.base,
+			issuer: match self.issuer {
+				Some(issuer) => Issuer::Shared(issuer),
+				None => Issuer::Owned(BlankIdIssuer::new()),
+			},
+			insert_blank_id: None,
 			result: ExpandedDocument::new(),
 		}
 	}
 }
 
+impl<I, V: Vocabulary> SerializeExpandedDocument<'_, I, V>
+where
+	V::Iri: Eq + Hash,
+	V::BlankId: Eq + Hash,
+{
+	/// Gives a fresh blank node identifier to the anonymous graphs and nodes
+	/// of the document.
+	///
+	/// This is done once the whole document is serialized, so that the
+	/// blank node identifiers it already uses can be reserved first.
+	fn identify_anonymous_nodes(&mut self, insert_blank_id: InsertBlankId<V>) {
+		let issuer = self.issuer.get();
+		for b in self.result.blank_ids() {
+			if let Some(b) = self.vocabulary.blank_id(b) {
+				issuer.reserve(b)
+			}
+		}
+
+		let objects = std::mem::take(&mut self.result).into_objects();
+		self.result =
+			identify_anonymous_objects(self.vocabulary, issuer, insert_blank_id, objects).into()
+	}
+}
+
+/// Gives a fresh blank node identifier to the anonymous nodes of `objects`,
+/// and of their graphs.
+fn identify_anonymous_objects<V: Vocabulary>(
+	vocabulary: &mut V,
+	issuer: &mut BlankIdIssuer,
+	insert_blank_id: InsertBlankId<V>,
+	objects: Graph<V::Iri, V::BlankId>,
+) -> Graph<V::Iri, V::BlankId>
+where
+	V::Iri: Eq + Hash,
+	V::BlankId: Eq + Hash,
+{
+	objects
+		.into_iter()
+		.map(|object| {
+			object.map_inner(|object| match object {
+				Object::Node(mut node) => {
+					if node.id.is_none() {
+						let prefix = if node.graph.is_some() {
+							"anonymous-graph-"
+						} else {
+							"anonymous-node-"
+						};
+
+						let blank_id = insert_blank_id(vocabulary, issuer.issue(prefix));
+						node.id = Some(json_ld_core_next::Id::Valid(rdf_types::Id::Blank(blank_id)))
+					}
+
+					if let Some(graph) = node.graph.take() {
+						node.graph = Some(identify_anonymous_objects(
+							vocabulary,
+							issuer,
+							insert_blank_id,
+							graph,
+						))
+					}
+
+					Object::Node(node)
+				}
+				object => object,
+			})
+		})
+		.collect()
+}
+
 impl<I: Interpretation, V: Vocabulary> linked_data_next::Visitor<I, V>
 	for SerializeExpandedDocument<'_, I, V>
 where
@@ -58,8 +440,13 @@ where
 	where
 		T: ?Sized + linked_data_next::LinkedDataGraph<I, V>,
 	{
-		let serializer =
-			SerializeDefaultGraph::new(self.vocabulary, self.interpretation, &mut self.result);
+		let serializer = SerializeDefaultGraph::new(
+			self.vocabulary,
+			self.interpretation,
+			self.options,
+			self.base,
+			&mut self.result,
+		);
 
 		value.visit_graph(serializer)
 	}
@@ -74,12 +461,16 @@ where
 			.lexical_representation(self.vocabulary, self.interpretation)
 			.map(CowRdfTerm::into_owned)
 		{
-			Some(Term::Literal(_)) => return Err(Error::InvalidGraph),
+			Some(Term::Literal(literal)) => {
+				return Err(Error::InvalidGraphName {
+					term: literal.to_string(),
+				})
+			}
 			Some(Term::Id(id)) => Node::with_id(json_ld_core_next::Id::Valid(id)),
 			None => Node::new(),
 		};
 
-		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation);
+		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation, self.options);
 
 		let graph = value.visit_graph(serializer)?;
 
@@ -89,7 +480,482 @@ where
 		Ok(())
 	}
 
-	fn end(self) -> Result<Self::Ok, Self::Error> {
+	fn end(mut self) -> Result<Self::Ok, Self::Error> {
+		if self.options.fold_lists {
+			crate::fold_lists_with(self.vocabulary, &mut self.result)
+		}
+
+		if let Some(insert_blank_id) = self.insert_blank_id {
+			self.identify_anonymous_nodes(insert_blank_id)
+		}
+
 		Ok(self.result)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use iref::IriBuf;
+	use json_ld_core_next::Id;
+	use linked_data_next::{
+		Anonymous, GraphVisitor, LinkedData, LinkedDataGraph, LinkedDataResource,
+		LinkedDataSubject, ResourceInterpretation, SubjectVisitor, Visitor,
+	};
+	use rdf_types::BlankIdBuf;
+
+	struct Named<T>(T);
+
+	impl<T: LinkedDataResource> LinkedDataResource for Named<T> {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			self.0.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl<T> LinkedDataGraph<(), ()> for Named<T> {
+		fn visit_graph<S: GraphVisitor<(), ()>>(&self, visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.end()
+		}
+	}
+
+	struct Dataset {
+		iri: IriBuf,
+		blank: BlankIdBuf,
+	}
+
+	impl LinkedData for Dataset {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.named_graph(&Named(self.iri.clone()))?;
+			visitor.named_graph(&Named(self.blank.clone()))?;
+			visitor.named_graph(&Named(Anonymous))?;
+			visitor.end()
+		}
+	}
+
+	struct LiteralNamedDataset;
+
+	impl LinkedData for LiteralNamedDataset {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.named_graph(&Named("not a graph name".to_owned()))?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn literal_graph_name() {
+		match crate::serialize(&LiteralNamedDataset) {
+			Err(crate::Error::InvalidGraphName { term }) => assert_eq!(term, "not a graph name"),
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn graph_names() {
+		let iri = IriBuf::new("http://example.org/g".to_owned()).unwrap();
+		let blank = BlankIdBuf::new("_:g".to_owned()).unwrap();
+		let document = super::serialize_expanded(&Dataset {
+			iri: iri.clone(),
+			blank: blank.clone(),
+		})
+		.unwrap();
+
+		let ids: Vec<_> = document
+			.iter()
+			.map(|object| {
+				let node = object.as_node().unwrap();
+				assert!(node.graph.is_some());
+				node.id.clone().unwrap()
+			})
+			.collect();
+
+		assert_eq!(ids.len(), 3);
+		assert!(ids.contains(&Id::iri(iri)));
+		assert!(ids.contains(&Id::blank(blank)));
+		assert!(ids.iter().any(|id| id
+			.as_blank()
+			.is_some_and(|b| b.as_str().starts_with("_:anonymous-graph-"))))
+	}
+
+	/// Graph containing a single subject.
+	struct Singleton<T>(T);
+
+	impl<T: LinkedDataResource + LinkedDataSubject> LinkedDataGraph<(), ()> for Singleton<T> {
+		fn visit_graph<S: GraphVisitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.subject(&self.0)?;
+			visitor.end()
+		}
+	}
+
+	/// Subject carrying its own graph.
+	struct GraphNode<G> {
+		id: IriBuf,
+		graph: G,
+	}
+
+	impl<G> LinkedDataResource for GraphNode<G> {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			self.id.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl<G: LinkedDataGraph<(), ()>> LinkedDataSubject for GraphNode<G> {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			visitor.graph(&self.graph)?;
+			visitor.end()
+		}
+	}
+
+	struct NestedDataset {
+		outer: IriBuf,
+		inner: IriBuf,
+		leaf: IriBuf,
+	}
+
+	impl LinkedData for NestedDataset {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.named_graph(&Named(self.outer.clone()))?;
+			visitor.default_graph(&Singleton(GraphNode {
+				id: self.outer.clone(),
+				graph: Singleton(GraphNode {
+					id: self.inner.clone(),
+					graph: Singleton(self.leaf.clone()),
+				}),
+			}))?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn nested_graphs() {
+		let outer = IriBuf::new("http://example.org/outer".to_owned()).unwrap();
+		let inner = IriBuf::new("http://example.org/inner".to_owned()).unwrap();
+		let leaf = IriBuf::new("http://example.org/leaf".to_owned()).unwrap();
+		let document = crate::serialize(&NestedDataset {
+			outer: outer.clone(),
+			inner: inner.clone(),
+			leaf: leaf.clone(),
+		})
+		.unwrap();
+
+		let outer_node = document
+			.iter()
+			.filter_map(|o| o.as_node())
+			.find(|n| {
+				n.id == Some(Id::iri(outer.clone()))
+					&& n.graph.as_ref().is_some_and(|g| !g.is_empty())
+			})
+			.expect("outer graph not found");
+
+		let inner_node = outer_node
+			.graph
+			.as_ref()
+			.unwrap()
+			.iter()
+			.next()
+			.unwrap()
+			.as_node()
+			.unwrap();
+		assert_eq!(inner_node.id, Some(Id::iri(inner)));
+
+		let leaf_node = inner_node
+			.graph
+			.as_ref()
+			.expect("inner graph lost")
+			.iter()
+			.next()
+			.unwrap();
+		assert_eq!(leaf_node.as_node().unwrap().id, Some(Id::iri(leaf)))
+	}
+
+	struct Subjects(Vec<IriBuf>);
+
+	impl LinkedDataGraph<(), ()> for Subjects {
+		fn visit_graph<S: GraphVisitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			for subject in &self.0 {
+				visitor.subject(subject)?;
+			}
+			visitor.end()
+		}
+	}
+
+	impl LinkedData for Subjects {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.default_graph(self)?;
+			visitor.end()
+		}
+	}
+
+	fn node_ids(base: Option<&iref::Iri>) -> Vec<Id> {
+		let subjects = Subjects(vec![
+			IriBuf::new("http://example.org/doc/a".to_owned()).unwrap(),
+			IriBuf::new("http://other.org/b".to_owned()).unwrap(),
+		]);
+
+		super::serialize_expanded_with_base(
+			&mut (),
+			&mut (),
+			crate::Options::default(),
+			base,
+			&subjects,
+		)
+		.unwrap()
+		.iter()
+		.map(|object| object.as_node().unwrap().id.clone().unwrap())
+		.collect()
+	}
+
+	#[test]
+	fn relative_node_ids() {
+		let base = iref::Iri::new("http://example.org/doc/").unwrap();
+		let ids = node_ids(Some(base));
+		assert_eq!(ids[0], Id::Invalid("a".to_owned()));
+		assert_eq!(ids[1].as_str(), "http://other.org/b");
+		assert!(ids[1].is_valid())
+	}
+
+	/// Default graph with an IRI subject and a blank node subject.
+	struct MixedSubjects {
+		iri: IriBuf,
+		blank: BlankIdBuf,
+	}
+
+	impl LinkedDataGraph<(), ()> for MixedSubjects {
+		fn visit_graph<S: GraphVisitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.subject(&self.iri)?;
+			visitor.subject(&self.blank)?;
+			visitor.end()
+		}
+	}
+
+	impl LinkedData for MixedSubjects {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.default_graph(self)?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn node_id_validity() {
+		let subjects = MixedSubjects {
+			iri: IriBuf::new("http://example.org/doc/a".to_owned()).unwrap(),
+			blank: BlankIdBuf::new("_:b".to_owned()).unwrap(),
+		};
+
+		let ids = |base| {
+			super::serialize_expanded_with_base(
+				&mut (),
+				&mut (),
+				crate::Options::default(),
+				base,
+				&subjects,
+			)
+			.unwrap()
+			.iter()
+			.map(|object| object.as_node().unwrap().id.clone().unwrap())
+			.collect::<Vec<_>>()
+		};
+
+		// Absolute IRIs and blank node identifiers are valid node identifiers.
+		let absolute = ids(None);
+		assert!(absolute.iter().all(Id::is_valid));
+		assert_eq!(absolute[0].as_str(), "http://example.org/doc/a");
+		assert_eq!(absolute[1].as_str(), "_:b");
+
+		// Relative references only appear when requested, and are kept as
+		// invalid identifiers by default.
+		let relative = ids(Some(iref::Iri::new("http://example.org/doc/").unwrap()));
+		assert_eq!(relative[0], Id::Invalid("a".to_owned()));
+		assert!(relative[1].is_valid())
+	}
+
+	#[test]
+	fn reject_relative_node_ids() {
+		let subjects = MixedSubjects {
+			iri: IriBuf::new("http://example.org/doc/a".to_owned()).unwrap(),
+			blank: BlankIdBuf::new("_:b".to_owned()).unwrap(),
+		};
+
+		let options = crate::Options {
+			relative_ids: crate::RelativeIdPolicy::Reject,
+			..Default::default()
+		};
+
+		let serialize =
+			|base| super::serialize_expanded_with_base(&mut (), &mut (), options, base, &subjects);
+
+		assert!(serialize(None).is_ok());
+		match serialize(Some(iref::Iri::new("http://example.org/doc/").unwrap())) {
+			Err(crate::Error::InvalidNodeId { value }) => assert_eq!(value, "a"),
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn builder_base() {
+		let subjects = Subjects(vec![
+			IriBuf::new("http://example.org/doc/a".to_owned()).unwrap()
+		]);
+		let base = iref::Iri::new("http://example.org/doc/").unwrap();
+
+		let (mut vocabulary, mut interpretation) = ((), ());
+		let serializer = super::SerializeExpandedDocumentBuilder::new()
+			.with_base(Some(base))
+			.build(&mut vocabulary, &mut interpretation);
+		let document = subjects.visit(serializer).unwrap();
+
+		let node = document.iter().next().unwrap().as_node().unwrap();
+		assert_eq!(node.id, Some(Id::Invalid("a".to_owned())))
+	}
+
+	#[test]
+	fn no_base() {
+		let ids = node_ids(None);
+		assert_eq!(ids[0].as_str(), "http://example.org/doc/a");
+		assert_eq!(ids[1].as_str(), "http://other.org/b")
+	}
+
+	/// Subject without identifier nor properties.
+	struct Blank;
+
+	impl LinkedDataResource for Blank {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			Anonymous.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl LinkedDataSubject for Blank {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(&self, visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.end()
+		}
+	}
+
+	/// Dataset with an anonymous default graph node and an anonymous graph.
+	struct AnonymousDataset;
+
+	impl LinkedData for AnonymousDataset {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.default_graph(&Singleton(Blank))?;
+			visitor.named_graph(&Named(Anonymous))?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn shared_issuer() {
+		let mut issuer = crate::BlankIdIssuer::new();
+		let mut ids = Vec::new();
+
+		for _ in 0..2 {
+			let document = super::serialize_expanded_with_issuer(
+				&mut (),
+				&mut (),
+				crate::Options::default(),
+				&mut issuer,
+				&AnonymousDataset,
+			)
+			.unwrap();
+
+			for object in document.iter() {
+				ids.push(object.as_node().unwrap().id.clone().unwrap())
+			}
+		}
+
+		assert_eq!(issuer.count(), 4);
+		assert_eq!(ids.len(), 4);
+		assert!(ids.iter().all(Id::is_blank));
+		for (i, id) in ids.iter().enumerate() {
+			assert!(!ids[i + 1..].contains(id), "duplicate identifier {id}")
+		}
+	}
+
+	#[test]
+	fn anonymous_nodes_without_issuer() {
+		let document = crate::serialize(&AnonymousDataset).unwrap();
+		assert_eq!(document.len(), 2);
+		assert!(document.iter().all(|o| o.as_node().unwrap().id.is_none()))
+	}
+
+	/// Anonymous graph holding a single subject.
+	struct AnonymousGraph<T>(T);
+
+	impl<T> LinkedDataResource for AnonymousGraph<T> {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			Anonymous.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl<T: LinkedDataResource + LinkedDataSubject> LinkedDataGraph<(), ()> for AnonymousGraph<T> {
+		fn visit_graph<S: GraphVisitor<(), ()>>(&self, visitor: S) -> Result<S::Ok, S::Error> {
+			Singleton(&self.0).visit_graph(visitor)
+		}
+	}
+
+	/// Dataset using a blank node identifier that the issuer would otherwise
+	/// give to its anonymous nodes.
+	struct ClashingDataset(BlankIdBuf);
+
+	impl LinkedData for ClashingDataset {
+		fn visit<S: Visitor<(), ()>>(&self, mut visitor: S) -> Result<S::Ok, S::Error> {
+			visitor.named_graph(&AnonymousGraph(Blank))?;
+			visitor.default_graph(&Singleton(self.0.clone()))?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn issued_ids_do_not_clash() {
+		let user = BlankIdBuf::new("_:anonymous-node-1".to_owned()).unwrap();
+		let mut issuer = crate::BlankIdIssuer::new();
+		let document = super::serialize_expanded_with_issuer(
+			&mut (),
+			&mut (),
+			crate::Options::default(),
+			&mut issuer,
+			&ClashingDataset(user.clone()),
+		)
+		.unwrap();
+
+		let graph = document
+			.iter()
+			.filter_map(|o| o.as_node())
+			.find(|n| n.graph.is_some())
+			.unwrap();
+		assert_eq!(
+			graph.id,
+			Some(Id::blank(
+				BlankIdBuf::new("_:anonymous-graph-0".to_owned()).unwrap()
+			))
+		);
+
+		let subject = graph.graph.as_ref().unwrap().iter().next().unwrap();
+		assert_eq!(
+			subject.as_node().unwrap().id,
+			Some(Id::blank(
+				BlankIdBuf::new("_:anonymous-node-2".to_owned()).unwrap()
+			))
+		);
+
+		assert!(document
+			.iter()
+			.any(|o| o.as_node().unwrap().id == Some(Id::blank(user.clone()))));
+		assert_eq!(document.blank_ids().len(), 3)
+	}
+}
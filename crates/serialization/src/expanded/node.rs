@@ -1,5 +1,5 @@
 use iref::Iri;
-use json_ld_core_next::{object::node::Multiset, Indexed, Node, Object};
+use json_ld_core_next::{object::Graph, Indexed, Node, Object};
 use linked_data_next::{CowRdfTerm, LinkedDataResource};
 use rdf_types::{
 	interpretation::{
@@ -8,9 +8,9 @@ use rdf_types::{
 	vocabulary::{IriVocabulary, IriVocabularyMut},
 	Interpretation, Term, Vocabulary, RDF_TYPE,
 };
-use std::hash::Hash;
+use std::{fmt, hash::Hash};
 
-use crate::Error;
+use crate::{Error, Options, TermPosition};
 
 use super::{
 	graph::SerializeGraph,
@@ -24,6 +24,26 @@ pub fn serialize_node_with<I, V, T>(
 	interpretation: &mut I,
 	value: &T,
 ) -> Result<Node<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
+{
+	serialize_node_with_options(vocabulary, interpretation, Options::default(), value)
+}
+
+/// Serialize the given Linked-Data value into a JSON-LD node object using a
+/// custom vocabulary, interpretation and serialization options.
+pub fn serialize_node_with_options<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	value: &T,
+) -> Result<Node<V::Iri, V::BlankId>, Error>
 where
 	V: Vocabulary + IriVocabularyMut,
 	V::Iri: Clone + Eq + Hash,
@@ -37,12 +57,17 @@ where
 		.lexical_representation(vocabulary, interpretation)
 		.map(CowRdfTerm::into_owned)
 	{
-		Some(Term::Literal(_)) => return Err(Error::InvalidNode),
+		Some(Term::Literal(literal)) => {
+			return Err(Error::InvalidLiteral {
+				position: TermPosition::Subject,
+				lexical: literal.to_string(),
+			})
+		}
 		Some(Term::Id(id)) => Some(json_ld_core_next::Id::Valid(id)),
 		None => None,
 	};
 
-	let serializer = SerializeNode::new(vocabulary, interpretation, id);
+	let serializer = SerializeNode::new(vocabulary, interpretation, options, id);
 
 	value.visit_subject(serializer)
 }
@@ -50,6 +75,7 @@ where
 pub struct SerializeNode<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	result: Node<V::Iri, V::BlankId>,
 }
 
@@ -57,6 +83,7 @@ impl<'a, I, V: Vocabulary> SerializeNode<'a, I, V> {
 	pub fn new(
 		vocabulary: &'a mut V,
 		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
 		id: Option<json_ld_core_next::Id<V::Iri, V::BlankId>>,
 	) -> Self {
 		let result = match id {
@@ -67,6 +94,7 @@ impl<'a, I, V: Vocabulary> SerializeNode<'a, I, V> {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			result,
 		}
 	}
@@ -95,30 +123,30 @@ where
 			.map(CowRdfTerm::into_owned)
 		{
 			Some(Term::Id(id)) => json_ld_core_next::Id::Valid(id),
-			_ => return Err(Error::InvalidPredicate),
+			other => return Err(predicate_error(other)),
 		};
 
-		let serializer = SerializeProperty::new(self.vocabulary, self.interpretation);
+		let serializer = SerializeProperty::new(self.vocabulary, self.interpretation, self.options);
 
 		let objects = value.visit_objects(serializer)?;
 
 		if is_iri(self.vocabulary, &prop, RDF_TYPE) {
-			let mut non_iri_objects = Multiset::new();
+			let mut non_iri_objects = Vec::new();
 
 			for obj in objects {
 				match into_type_value(obj) {
 					Ok(ty) => self.result.types_mut_or_default().push(ty),
-					Err(obj) => {
-						non_iri_objects.insert(obj);
-					}
+					Err(obj) => non_iri_objects.push(obj),
 				}
 			}
 
 			if !non_iri_objects.is_empty() {
-				self.result.properties_mut().set(prop, non_iri_objects);
+				self.result
+					.properties_mut()
+					.insert_all(prop, non_iri_objects);
 			}
 		} else {
-			self.result.properties_mut().set(prop, objects);
+			self.result.properties_mut().insert_all(prop, objects);
 		}
 
 		Ok(())
@@ -134,15 +162,19 @@ where
 			.map(CowRdfTerm::into_owned)
 		{
 			Some(Term::Id(id)) => json_ld_core_next::Id::Valid(id),
-			_ => return Err(Error::InvalidPredicate),
+			other => return Err(predicate_error(other)),
 		};
 
-		let serializer = SerializeReverseProperty::new(self.vocabulary, self.interpretation);
+		let serializer =
+			SerializeReverseProperty::new(self.vocabulary, self.interpretation, self.options);
 
+		// Reverse properties are stored apart from the forward properties, so
+		// the same predicate may be used in both directions. It may also be
+		// visited more than once, hence the objects are accumulated.
 		let objects = value.visit_objects(serializer)?;
 		self.result
 			.reverse_properties_mut_or_default()
-			.set(prop, objects);
+			.insert_all(prop, objects);
 
 		Ok(())
 	}
@@ -151,7 +183,8 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		let node = serialize_node_with(self.vocabulary, self.interpretation, value)?;
+		let node =
+			serialize_node_with_options(self.vocabulary, self.interpretation, self.options, value)?;
 
 		self.result
 			.included_mut_or_default()
@@ -163,10 +196,15 @@ where
 	where
 		T: ?Sized + linked_data_next::LinkedDataGraph<I, V>,
 	{
-		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation);
+		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation, self.options);
 
+		// A subject may be visited through more than one graph, in which case
+		// the graphs are merged.
 		let graph = value.visit_graph(serializer)?;
-		self.result.graph = Some(graph);
+		self.result
+			.graph
+			.get_or_insert_with(Graph::new)
+			.extend(graph);
 		Ok(())
 	}
 
@@ -193,6 +231,19 @@ pub(crate) fn into_type_value<I, B>(
 	}
 }
 
+/// Returns the error raised for a predicate that is not an identifier.
+pub(crate) fn predicate_error<T, L: fmt::Display>(repr: Option<Term<T, L>>) -> Error {
+	match repr {
+		Some(Term::Literal(literal)) => Error::InvalidLiteral {
+			position: TermPosition::Predicate,
+			lexical: literal.to_string(),
+		},
+		_ => Error::UnresolvableIri {
+			position: TermPosition::Predicate,
+		},
+	}
+}
+
 pub(crate) fn is_iri<V, B>(vocabulary: &V, id: &json_ld_core_next::Id<V::Iri, B>, iri: &Iri) -> bool
 where
 	V: IriVocabulary,
@@ -205,3 +256,208 @@ where
 		_ => false,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use iref::IriBuf;
+	use linked_data_next::{LinkedDataSubject, ResourceInterpretation, SubjectVisitor};
+
+	const KNOWS: &str = "http://example.org/knows";
+
+	struct Person {
+		id: IriBuf,
+		knows: IriBuf,
+		known_by: [IriBuf; 2],
+	}
+
+	impl LinkedDataResource for Person {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			self.id.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl LinkedDataSubject for Person {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			let knows = Iri::new(KNOWS).unwrap();
+			visitor.predicate(knows, &self.knows)?;
+			for other in &self.known_by {
+				visitor.reverse_predicate(knows, other)?;
+			}
+			visitor.end()
+		}
+	}
+
+	fn iri(s: &str) -> IriBuf {
+		IriBuf::new(s.to_owned()).unwrap()
+	}
+
+	struct Subjects(Vec<IriBuf>);
+
+	impl linked_data_next::LinkedDataGraph<(), ()> for Subjects {
+		fn visit_graph<S: linked_data_next::GraphVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			for subject in &self.0 {
+				visitor.subject(subject)?;
+			}
+			visitor.end()
+		}
+	}
+
+	/// Subject carrying its own graph, visited in two parts.
+	struct GraphNode {
+		id: IriBuf,
+		graphs: [Subjects; 2],
+	}
+
+	impl LinkedDataResource for GraphNode {
+		fn interpretation(
+			&self,
+			vocabulary: &mut (),
+			interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			self.id.interpretation(vocabulary, interpretation)
+		}
+	}
+
+	impl LinkedDataSubject for GraphNode {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			for graph in &self.graphs {
+				visitor.graph(graph)?;
+			}
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn graph() {
+		let node = GraphNode {
+			id: iri("http://example.org/g"),
+			graphs: [
+				Subjects(vec![iri("http://example.org/a")]),
+				Subjects(vec![iri("http://example.org/b")]),
+			],
+		};
+
+		let node = serialize_node_with(&mut (), &mut (), &node).unwrap();
+		assert_eq!(
+			node.id,
+			Some(json_ld_core_next::Id::iri(iri("http://example.org/g")))
+		);
+
+		let mut ids: Vec<_> = node
+			.graph
+			.expect("missing graph")
+			.iter()
+			.map(|o| {
+				o.as_node()
+					.unwrap()
+					.id
+					.as_ref()
+					.unwrap()
+					.as_str()
+					.to_owned()
+			})
+			.collect();
+		ids.sort();
+		assert_eq!(ids, ["http://example.org/a", "http://example.org/b"])
+	}
+
+	#[test]
+	fn reverse_relation() {
+		let person = Person {
+			id: iri("http://example.org/a"),
+			knows: iri("http://example.org/b"),
+			known_by: [iri("http://example.org/c"), iri("http://example.org/d")],
+		};
+
+		let node = serialize_node_with(&mut (), &mut (), &person).unwrap();
+		let knows = json_ld_core_next::Id::iri(iri(KNOWS));
+
+		let forward: Vec<_> = node
+			.get(&knows)
+			.map(|o| o.as_node().unwrap().id.clone().unwrap())
+			.collect();
+		assert_eq!(
+			forward,
+			[json_ld_core_next::Id::iri(iri("http://example.org/b"))]
+		);
+
+		let reverse = node.reverse_properties.as_ref().unwrap();
+		let mut backward: Vec<_> = reverse
+			.get(&knows)
+			.map(|n| n.id.as_ref().unwrap().as_str().to_owned())
+			.collect();
+		backward.sort();
+		assert_eq!(backward, ["http://example.org/c", "http://example.org/d"])
+	}
+
+	/// Anonymous subject using `P` as its only predicate.
+	struct WithPredicate<P>(P);
+
+	impl<P> LinkedDataResource for WithPredicate<P> {
+		fn interpretation(
+			&self,
+			_vocabulary: &mut (),
+			_interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			ResourceInterpretation::Uninterpreted(None)
+		}
+	}
+
+	impl<P: LinkedDataResource> LinkedDataSubject for WithPredicate<P> {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			visitor.predicate(&self.0, &iri("http://example.org/b"))?;
+			visitor.end()
+		}
+	}
+
+	#[test]
+	fn literal_predicate() {
+		let subject = WithPredicate("not a predicate".to_owned());
+		match serialize_node_with(&mut (), &mut (), &subject) {
+			Err(Error::InvalidLiteral { position, lexical }) => {
+				assert_eq!(position, TermPosition::Predicate);
+				assert_eq!(lexical, "not a predicate")
+			}
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn unresolvable_predicate() {
+		let subject = WithPredicate(linked_data_next::Anonymous);
+		match serialize_node_with(&mut (), &mut (), &subject) {
+			Err(Error::UnresolvableIri { position }) => {
+				assert_eq!(position, TermPosition::Predicate)
+			}
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn literal_subject() {
+		match serialize_node_with(&mut (), &mut (), &"not a node".to_owned()) {
+			Err(Error::InvalidLiteral { position, lexical }) => {
+				assert_eq!(position, TermPosition::Subject);
+				assert_eq!(lexical, "not a node")
+			}
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+}
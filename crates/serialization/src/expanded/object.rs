@@ -2,7 +2,7 @@ use std::hash::Hash;
 
 use json_ld_core_next::{
 	object::{
-		node::{Included, Multiset, Properties, ReverseProperties},
+		node::{Included, Properties, ReverseProperties},
 		Graph, List,
 	},
 	rdf::{RDF_FIRST, RDF_REST, RDF_TYPE},
@@ -17,14 +17,14 @@ use rdf_types::{
 	Id, Interpretation, Term, Vocabulary,
 };
 
-use crate::Error;
+use crate::{Error, Options, TermPosition};
 
 use super::{
 	graph::SerializeGraph,
 	list::{SerializeListFirst, SerializeListRest},
-	node::{into_type_value, is_iri, SerializeNode},
+	node::{into_type_value, is_iri, predicate_error, SerializeNode},
 	property::{SerializeProperty, SerializeReverseProperty},
-	serialize_node_with,
+	serialize_node_with_options,
 	value::literal_to_value,
 };
 
@@ -35,6 +35,26 @@ pub fn serialize_object_with<I, V, T>(
 	interpretation: &mut I,
 	value: &T,
 ) -> Result<Object<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+	T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
+{
+	serialize_object_with_options(vocabulary, interpretation, Options::default(), value)
+}
+
+/// Serialize the given Linked-Data value into a JSON-LD object using a
+/// custom vocabulary, interpretation and serialization options.
+pub fn serialize_object_with_options<I, V, T>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	options: Options<V::Iri>,
+	value: &T,
+) -> Result<Object<V::Iri, V::BlankId>, Error>
 where
 	V: Vocabulary + IriVocabularyMut,
 	V::Iri: Clone + Eq + Hash,
@@ -49,20 +69,21 @@ where
 		.map(CowRdfTerm::into_owned)
 	{
 		Some(Term::Literal(lit)) => {
-			let value = literal_to_value(vocabulary, lit);
+			let value = literal_to_value(vocabulary, lit, options)?;
 			Ok(Object::Value(value))
 		}
 		Some(Term::Id(id)) => {
 			let serializer = SerializeNode::new(
 				vocabulary,
 				interpretation,
+				options,
 				Some(json_ld_core_next::Id::Valid(id)),
 			);
 
 			Ok(Object::node(value.visit_subject(serializer)?))
 		}
 		None => {
-			let serializer = SerializeObject::new(vocabulary, interpretation);
+			let serializer = SerializeObject::new(vocabulary, interpretation, options);
 
 			value.visit_subject(serializer)
 		}
@@ -72,6 +93,7 @@ where
 pub struct SerializeObject<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	types: Vec<json_ld_core_next::Id<V::Iri, V::BlankId>>,
 	properties: Properties<V::Iri, V::BlankId>,
 	reverse_properties: ReverseProperties<V::Iri, V::BlankId>,
@@ -82,10 +104,15 @@ pub struct SerializeObject<'a, I, V: Vocabulary> {
 }
 
 impl<'a, I, V: Vocabulary> SerializeObject<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			types: Vec::new(),
 			properties: Properties::new(),
 			reverse_properties: ReverseProperties::new(),
@@ -121,44 +148,50 @@ where
 		{
 			Some(Term::Id(id)) => {
 				if let Id::Iri(iri) = &id {
-					let iri = self.vocabulary.iri(iri).unwrap();
+					let iri = self.vocabulary.iri(iri).ok_or(Error::UnresolvableIri {
+						position: TermPosition::Predicate,
+					})?;
 					if iri == RDF_FIRST {
-						let serializer =
-							SerializeListFirst::new(self.vocabulary, self.interpretation);
+						let serializer = SerializeListFirst::new(
+							self.vocabulary,
+							self.interpretation,
+							self.options,
+						);
 						self.first = value.visit_objects(serializer)?;
 					} else if iri == RDF_REST {
-						let serializer =
-							SerializeListRest::new(self.vocabulary, self.interpretation);
+						let serializer = SerializeListRest::new(
+							self.vocabulary,
+							self.interpretation,
+							self.options,
+						);
 						self.rest = Some(value.visit_objects(serializer)?);
 					}
 				}
 
 				json_ld_core_next::Id::Valid(id)
 			}
-			_ => return Err(Error::InvalidPredicate),
+			other => return Err(predicate_error(other)),
 		};
 
-		let serializer = SerializeProperty::new(self.vocabulary, self.interpretation);
+		let serializer = SerializeProperty::new(self.vocabulary, self.interpretation, self.options);
 
 		let objects = value.visit_objects(serializer)?;
 
 		if is_iri(self.vocabulary, &prop, RDF_TYPE) {
-			let mut non_iri_objects = Multiset::new();
+			let mut non_iri_objects = Vec::new();
 
 			for obj in objects {
 				match into_type_value(obj) {
 					Ok(ty) => self.types.push(ty),
-					Err(obj) => {
-						non_iri_objects.insert(obj);
-					}
+					Err(obj) => non_iri_objects.push(obj),
 				}
 			}
 
 			if !non_iri_objects.is_empty() {
-				self.properties.set(prop, non_iri_objects);
+				self.properties.insert_all(prop, non_iri_objects);
 			}
 		} else {
-			self.properties.set(prop, objects);
+			self.properties.insert_all(prop, objects);
 		}
 
 		Ok(())
@@ -174,13 +207,14 @@ where
 			.map(CowRdfTerm::into_owned)
 		{
 			Some(Term::Id(id)) => json_ld_core_next::Id::Valid(id),
-			_ => return Err(Error::InvalidPredicate),
+			other => return Err(predicate_error(other)),
 		};
 
-		let serializer = SerializeReverseProperty::new(self.vocabulary, self.interpretation);
+		let serializer =
+			SerializeReverseProperty::new(self.vocabulary, self.interpretation, self.options);
 
 		let objects = value.visit_objects(serializer)?;
-		self.reverse_properties.set(prop, objects);
+		self.reverse_properties.insert_all(prop, objects);
 
 		Ok(())
 	}
@@ -189,7 +223,8 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		let node = serialize_node_with(self.vocabulary, self.interpretation, value)?;
+		let node =
+			serialize_node_with_options(self.vocabulary, self.interpretation, self.options, value)?;
 		self.included.insert(Indexed::none(node));
 		Ok(())
 	}
@@ -198,8 +233,9 @@ where
 	where
 		T: ?Sized + linked_data_next::LinkedDataGraph<I, V>,
 	{
-		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation);
-		self.graph = Some(value.visit_graph(serializer)?);
+		let serializer = SerializeGraph::new(self.vocabulary, self.interpretation, self.options);
+		let graph = value.visit_graph(serializer)?;
+		self.graph.get_or_insert_with(Graph::new).extend(graph);
 		Ok(())
 	}
 
@@ -253,3 +289,69 @@ where
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use iref::{Iri, IriBuf};
+	use linked_data_next::{LinkedDataSubject, ResourceInterpretation, SubjectVisitor};
+
+	const KNOWS: &str = "http://example.org/knows";
+
+	fn iri(s: &str) -> IriBuf {
+		IriBuf::new(s.to_owned()).unwrap()
+	}
+
+	/// Anonymous subject visiting the same predicate twice in each direction.
+	struct Anonymous;
+
+	impl LinkedDataResource for Anonymous {
+		fn interpretation(
+			&self,
+			_vocabulary: &mut (),
+			_interpretation: &mut (),
+		) -> ResourceInterpretation<'_, (), ()> {
+			ResourceInterpretation::Uninterpreted(None)
+		}
+	}
+
+	impl LinkedDataSubject for Anonymous {
+		fn visit_subject<S: SubjectVisitor<(), ()>>(
+			&self,
+			mut visitor: S,
+		) -> Result<S::Ok, S::Error> {
+			let knows = Iri::new(KNOWS).unwrap();
+			visitor.predicate(knows, &iri("http://example.org/a"))?;
+			visitor.predicate(knows, &iri("http://example.org/b"))?;
+			visitor.reverse_predicate(knows, &iri("http://example.org/c"))?;
+			visitor.reverse_predicate(knows, &iri("http://example.org/d"))?;
+			visitor.end()
+		}
+	}
+
+	fn ids<'a>(nodes: impl Iterator<Item = &'a Node>) -> Vec<String> {
+		let mut ids: Vec<_> = nodes
+			.map(|n| n.id.as_ref().unwrap().as_str().to_owned())
+			.collect();
+		ids.sort();
+		ids
+	}
+
+	#[test]
+	fn repeated_predicates() {
+		let object = serialize_object_with(&mut (), &mut (), &Anonymous).unwrap();
+		let node = object.as_node().unwrap();
+		let knows = json_ld_core_next::Id::iri(iri(KNOWS));
+
+		assert_eq!(
+			ids(node.get(&knows).map(|o| o.as_node().unwrap())),
+			["http://example.org/a", "http://example.org/b"]
+		);
+
+		let reverse = node.reverse_properties.as_ref().unwrap();
+		assert_eq!(
+			ids(reverse.get(&knows).map(|n| n.inner())),
+			["http://example.org/c", "http://example.org/d"]
+		)
+	}
+}
@@ -10,21 +10,27 @@ use rdf_types::{
 	Interpretation, Vocabulary,
 };
 
-use crate::Error;
+use crate::{Error, Options};
 
-use super::{object::serialize_object_with, serialize_node_with};
+use super::{object::serialize_object_with_options, serialize_node_with_options};
 
 pub struct SerializeProperty<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	result: Multiset<IndexedObject<V::Iri, V::BlankId>>,
 }
 
 impl<'a, I, V: Vocabulary> SerializeProperty<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			result: Multiset::new(),
 		}
 	}
@@ -47,7 +53,12 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		let object = serialize_object_with(self.vocabulary, self.interpretation, value)?;
+		let object = serialize_object_with_options(
+			self.vocabulary,
+			self.interpretation,
+			self.options,
+			value,
+		)?;
 		self.result.insert(Indexed::none(object));
 		Ok(())
 	}
@@ -60,14 +71,20 @@ where
 pub struct SerializeReverseProperty<'a, I, V: Vocabulary> {
 	vocabulary: &'a mut V,
 	interpretation: &'a mut I,
+	options: Options<'a, V::Iri>,
 	result: Multiset<IndexedNode<V::Iri, V::BlankId>>,
 }
 
 impl<'a, I, V: Vocabulary> SerializeReverseProperty<'a, I, V> {
-	pub fn new(vocabulary: &'a mut V, interpretation: &'a mut I) -> Self {
+	pub fn new(
+		vocabulary: &'a mut V,
+		interpretation: &'a mut I,
+		options: Options<'a, V::Iri>,
+	) -> Self {
 		Self {
 			vocabulary,
 			interpretation,
+			options,
 			result: Multiset::new(),
 		}
 	}
@@ -90,7 +107,8 @@ where
 	where
 		T: ?Sized + LinkedDataResource<I, V> + linked_data_next::LinkedDataSubject<I, V>,
 	{
-		let object = serialize_node_with(self.vocabulary, self.interpretation, value)?;
+		let object =
+			serialize_node_with_options(self.vocabulary, self.interpretation, self.options, value)?;
 		self.result.insert(Indexed::none(object));
 		Ok(())
 	}
@@ -1,37 +1,167 @@
-use json_ld_core_next::{object::Literal, LangString, Value};
+use iref::Iri;
+use json_ld_core_next::{object::Literal, Direction, LangString, LenientLangTagBuf, Value};
 use linked_data_next::RdfLiteral;
-use rdf_types::{vocabulary::IriVocabularyMut, LiteralType};
+use rdf_types::{vocabulary::IriVocabularyMut, LiteralType, RDF_LANG_STRING};
 use xsd_types::XSD_STRING;
 
+use crate::{Error, LanguageDatatypePolicy, Options, Warning};
+
 pub fn literal_to_value<V: IriVocabularyMut>(
 	vocabulary: &mut V,
 	lit: RdfLiteral<V>,
-) -> Value<V::Iri> {
-	match lit {
+	options: Options<V::Iri>,
+) -> Result<Value<V::Iri>, Error> {
+	let value = match lit {
 		RdfLiteral::Any(s, ty) => match ty {
 			LiteralType::Any(iri) => {
-				let literal_ty = if vocabulary.iri(&iri).unwrap() == XSD_STRING {
-					None
-				} else {
-					Some(iri)
-				};
+				let resolved = vocabulary.iri(&iri);
+				if resolved.is_some_and(|i| i == XSD_STRING) {
+					Value::Literal(Literal::String(s.into()), None)
+				} else if let Some((language, direction)) = resolved.and_then(i18n_datatype) {
+					lang_string(s, language, Some(direction))?
+				} else if let Some(i) = resolved.filter(|i| is_lang_string_datatype(i)) {
+					match options.language_datatype {
+						LanguageDatatypePolicy::Normalize => {
+							options.warn(Warning::DroppedDatatype {
+								datatype: i.to_string(),
+							})
+						}
+						LanguageDatatypePolicy::Reject => {
+							return Err(Error::LanguageDatatype {
+								datatype: i.to_string(),
+							})
+						}
+					}
 
-				Value::Literal(Literal::String(s.into()), literal_ty)
+					Value::Literal(Literal::String(s.into()), None)
+				} else if let Some(value) = match resolved {
+					Some(i) => convert(options, i, &s)?,
+					None => None,
+				} {
+					value
+				} else {
+					Value::Literal(Literal::String(s.into()), Some(iri))
+				}
+			}
+			LiteralType::LangString(language) => lang_string(s, Some(language.as_str()), None)?,
+		},
+		RdfLiteral::Xsd(xsd) => xsd_to_value(vocabulary, xsd, options)?,
+		RdfLiteral::Json(mut json) => {
+			if options.canonical_json {
+				sort_json_keys(&mut json)
 			}
-			LiteralType::LangString(language) => {
-				Value::LangString(LangString::new(s.into(), Some(language.into()), None).unwrap())
+
+			Value::Json(json)
+		}
+	};
+
+	Ok(value)
+}
+
+/// Builds a language string value.
+///
+/// An empty language tag is ignored, hence a plain string is produced if no
+/// direction is given either.
+fn lang_string<T>(
+	s: String,
+	language: Option<&str>,
+	direction: Option<Direction>,
+) -> Result<Value<T>, Error> {
+	let language = match language.filter(|tag| !tag.is_empty()) {
+		Some(tag) => match LenientLangTagBuf::new(tag.to_owned()) {
+			(tag, None) => Some(tag),
+			(tag, Some(_)) => {
+				return Err(Error::InvalidLanguageTag {
+					tag: tag.into_string(),
+				})
 			}
 		},
-		RdfLiteral::Xsd(xsd) => xsd_to_value(vocabulary, xsd),
-		RdfLiteral::Json(json) => Value::Json(json),
+		None => None,
+	};
+
+	match LangString::new(s.into(), language, direction) {
+		Ok(s) => Ok(Value::LangString(s)),
+		Err(s) => Ok(Value::Literal(Literal::String(s), None)),
 	}
 }
 
-fn xsd_to_value<V: IriVocabularyMut>(vocabulary: &mut V, value: xsd_types::Value) -> Value<V::Iri> {
-	let ty = value.datatype();
+/// Recursively sorts the entries of every object in the given JSON value by
+/// key.
+fn sort_json_keys(json: &mut json_syntax::Value) {
+	match json {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				sort_json_keys(item)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for (_, value) in object.iter_mut() {
+				sort_json_keys(value)
+			}
+
+			object.sort()
+		}
+		_ => (),
+	}
+}
+
+const I18N: &str = "https://www.w3.org/ns/i18n#";
+
+const RDF_DIR_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#dirLangString";
+
+/// Checks if the given datatype is one of the language string datatypes,
+/// which cannot be used without a language tag.
+fn is_lang_string_datatype(iri: &Iri) -> bool {
+	iri == RDF_LANG_STRING || iri.as_str() == RDF_DIR_LANG_STRING
+}
+
+/// Extracts the language and base direction encoded in an `i18n` datatype
+/// IRI, of the form `https://www.w3.org/ns/i18n#language_direction` or
+/// `https://www.w3.org/ns/i18n#direction`.
+fn i18n_datatype(iri: &Iri) -> Option<(Option<&str>, Direction)> {
+	let suffix = iri.as_str().strip_prefix(I18N)?;
+	match suffix.rsplit_once('_') {
+		Some((language, direction)) => Some((Some(language), direction.parse().ok()?)),
+		None => Some((None, suffix.parse().ok()?)),
+	}
+}
+
+// <https://www.w3.org/TR/xmlschema11-2/#f-doubleLexmap>
+const XSD_CANONICAL_FLOAT: pretty_dtoa::FmtFloatConfig = pretty_dtoa::FmtFloatConfig::default()
+	.force_e_notation()
+	.capitalize_e(true);
+
+/// Converts the given lexical form using the custom datatype converters, if
+/// any.
+fn convert<T>(
+	options: Options<T>,
+	datatype: &Iri,
+	lexical: &str,
+) -> Result<Option<Value<T>>, Error> {
+	let Some(result) = options
+		.datatypes
+		.and_then(|datatypes| datatypes.try_convert(datatype, lexical))
+	else {
+		return Ok(None);
+	};
+
+	result.map(Some).map_err(|error| Error::CustomConverter {
+		datatype: datatype.to_string(),
+		lexical: lexical.to_owned(),
+		error,
+	})
+}
+
+/// Formats the given XSD numeric value into the canonical JSON number
+/// representation expected by the RDF to object conversion.
+///
+/// Integers are formatted as bare integers, whatever their magnitude, and
+/// finite doubles and floats in the `d.dddE±d` scientific notation. Returns
+/// `None` if the value is not a number, or not a finite one.
+///
+/// See <https://www.w3.org/TR/json-ld11-api/#data-round-tripping>.
+fn canonical_number(value: &xsd_types::Value) -> Option<String> {
 	let number = match value {
-		xsd_types::Value::Boolean(b) => return Value::Literal(Literal::Boolean(b.into()), None),
-		xsd_types::Value::String(s) => return Value::Literal(Literal::String(s.into()), None),
 		xsd_types::Value::Decimal(v) => v.to_string(),
 		xsd_types::Value::Integer(v) => v.to_string(),
 		xsd_types::Value::NonPositiveInteger(v) => v.to_string(),
@@ -46,20 +176,479 @@ fn xsd_to_value<V: IriVocabularyMut>(vocabulary: &mut V, value: xsd_types::Value
 		xsd_types::Value::UnsignedShort(v) => v.to_string(),
 		xsd_types::Value::UnsignedByte(v) => v.to_string(),
 		xsd_types::Value::PositiveInteger(v) => v.to_string(),
-		other => {
-			let ty = vocabulary.insert(ty.iri());
-			return Value::Literal(Literal::String(other.to_string().into()), Some(ty));
+		xsd_types::Value::Double(v) if v.is_finite() => {
+			pretty_dtoa::dtoa(v.into_f64(), XSD_CANONICAL_FLOAT)
+		}
+		xsd_types::Value::Float(v) if v.is_finite() => {
+			pretty_dtoa::ftoa(v.into_f32(), XSD_CANONICAL_FLOAT)
+		}
+		_ => return None,
+	};
+
+	Some(number)
+}
+
+fn xsd_to_value<V: IriVocabularyMut>(
+	vocabulary: &mut V,
+	value: xsd_types::Value,
+	options: Options<V::Iri>,
+) -> Result<Value<V::Iri>, Error> {
+	let ty = value.datatype();
+	let number = match value {
+		xsd_types::Value::Boolean(b) => {
+			return Ok(Value::Literal(Literal::Boolean(b.into()), None))
 		}
+		xsd_types::Value::String(s) => return Ok(Value::Literal(Literal::String(s.into()), None)),
+		other => match canonical_number(&other) {
+			Some(number) => number,
+			None => {
+				let lexical = other.to_string();
+				if let Some(value) = convert(options, ty.iri(), &lexical)? {
+					return Ok(value);
+				}
+
+				// Non-finite numbers have no JSON representation.
+				if matches!(
+					other,
+					xsd_types::Value::Double(_) | xsd_types::Value::Float(_)
+				) {
+					options.warn(Warning::CoercedLiteral {
+						lexical: lexical.clone(),
+						datatype: ty.iri().to_string(),
+					})
+				}
+
+				let ty = vocabulary.insert(ty.iri());
+				return Ok(Value::Literal(Literal::String(lexical.into()), Some(ty)));
+			}
+		},
 	};
 
-	match json_syntax::Number::new(&number) {
-		Ok(_) => {
-			let n = unsafe { json_syntax::NumberBuf::new_unchecked(number.into_bytes().into()) };
+	let value = match normalize_number(&number) {
+		Some(n) => {
+			let n = unsafe { json_syntax::NumberBuf::new_unchecked(n.into_bytes().into()) };
 			Value::Literal(Literal::Number(n), None)
 		}
-		Err(_) => {
+		None => {
+			options.warn(Warning::CoercedLiteral {
+				lexical: number.clone(),
+				datatype: ty.iri().to_string(),
+			});
+
 			let ty = vocabulary.insert(ty.iri());
 			Value::Literal(Literal::String(number.into()), Some(ty))
 		}
+	};
+
+	Ok(value)
+}
+
+/// Rewrites the given numeric lexical form into a valid JSON number.
+///
+/// Lexical forms such as those of `xsd:decimal` allow a leading `+`, leading
+/// zeros and a decimal point without digits on one side (`.5`, `5.`), none of
+/// which JSON allows. The exponent marker is normalized to `E`. Returns `None`
+/// if the lexical form is not numeric.
+fn normalize_number(lexical: &str) -> Option<String> {
+	let (sign, unsigned) = match lexical.strip_prefix('-') {
+		Some(rest) => ("-", rest),
+		None => ("", lexical.strip_prefix('+').unwrap_or(lexical)),
+	};
+
+	let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+		Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+		None => (unsigned, None),
+	};
+
+	let (integer, fraction) = match mantissa.split_once('.') {
+		Some((integer, fraction)) => (integer, Some(fraction)),
+		None => (mantissa, None),
+	};
+
+	let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+	let fraction_digits = fraction.unwrap_or_default();
+	let no_digits = integer.is_empty() && fraction_digits.is_empty();
+	if no_digits || !is_digits(integer) || !is_digits(fraction_digits) {
+		return None;
+	}
+
+	let mut result = sign.to_owned();
+	match integer.trim_start_matches('0') {
+		"" => result.push('0'),
+		integer => result.push_str(integer),
+	}
+
+	if let Some(fraction) = fraction {
+		result.push('.');
+		match fraction {
+			"" => result.push('0'),
+			fraction => result.push_str(fraction),
+		}
+	}
+
+	if let Some(exponent) = exponent {
+		let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+		if digits.is_empty() || !is_digits(digits) {
+			return None;
+		}
+
+		result.push('E');
+		result.push_str(exponent)
+	}
+
+	json_syntax::Number::new(&result).is_ok().then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_syntax::{Parse, Print};
+	use rdf_types::vocabulary::{IndexVocabulary, IriIndex};
+
+	fn lang_string(
+		vocabulary: &mut IndexVocabulary,
+		lit: RdfLiteral<IndexVocabulary>,
+	) -> LangString {
+		match literal_to_value(vocabulary, lit, Options::default()).unwrap() {
+			Value::LangString(s) => s,
+			other => panic!("expected a language string, found {other:?}"),
+		}
+	}
+
+	fn i18n_literal(
+		vocabulary: &mut IndexVocabulary,
+		value: &str,
+		ty: &str,
+	) -> RdfLiteral<IndexVocabulary> {
+		let ty = vocabulary.insert(Iri::new(ty).unwrap());
+		RdfLiteral::Any(value.to_owned(), LiteralType::Any(ty))
+	}
+
+	#[test]
+	fn unknown_datatype_iri() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let ty = IriIndex::from(42);
+		let lit = RdfLiteral::Any("foo".to_owned(), LiteralType::Any(ty));
+
+		match literal_to_value(&mut vocabulary, lit, Options::default()).unwrap() {
+			Value::Literal(Literal::String(s), Some(t)) => {
+				assert_eq!(s.as_str(), "foo");
+				assert_eq!(t, ty)
+			}
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn i18n_ltr() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"Hello",
+			"https://www.w3.org/ns/i18n#en_ltr",
+		);
+		let s = lang_string(&mut vocabulary, lit);
+
+		assert_eq!(s.as_str(), "Hello");
+		assert_eq!(s.language().map(|l| l.as_str()), Some("en"));
+		assert_eq!(s.direction(), Some(Direction::Ltr))
+	}
+
+	#[test]
+	fn i18n_rtl() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"مرحبا",
+			"https://www.w3.org/ns/i18n#ar_rtl",
+		);
+		let s = lang_string(&mut vocabulary, lit);
+
+		assert_eq!(s.language().map(|l| l.as_str()), Some("ar"));
+		assert_eq!(s.direction(), Some(Direction::Rtl));
+
+		let lit = i18n_literal(&mut vocabulary, "مرحبا", "https://www.w3.org/ns/i18n#rtl");
+		let s = lang_string(&mut vocabulary, lit);
+
+		assert_eq!(s.language(), None);
+		assert_eq!(s.direction(), Some(Direction::Rtl))
+	}
+
+	#[test]
+	fn language_datatype_policy() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let options = Options {
+			language_datatype: LanguageDatatypePolicy::Reject,
+			..Options::default()
+		};
+
+		// The language and direction encoded by `i18n` datatypes do not
+		// conflict.
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"salut",
+			"https://www.w3.org/ns/i18n#fr_ltr",
+		);
+		match literal_to_value(&mut vocabulary, lit, options).unwrap() {
+			Value::LangString(s) => {
+				assert_eq!(s.language().map(|l| l.as_str()), Some("fr"));
+				assert_eq!(s.direction(), Some(Direction::Ltr))
+			}
+			other => panic!("expected a language string, found {other:?}"),
+		}
+
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"Hello",
+			"http://www.w3.org/1999/02/22-rdf-syntax-ns#langString",
+		);
+		match literal_to_value(&mut vocabulary, lit, options) {
+			Err(Error::LanguageDatatype { datatype }) => {
+				assert_eq!(
+					datatype,
+					"http://www.w3.org/1999/02/22-rdf-syntax-ns#langString"
+				)
+			}
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn language_datatype_warning() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let warnings = crate::Warnings::new();
+		let options = Options {
+			warnings: Some(&warnings),
+			..Options::default()
+		};
+
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"Hello",
+			"https://www.w3.org/ns/i18n#en_ltr",
+		);
+		assert!(matches!(
+			literal_to_value(&mut vocabulary, lit, options).unwrap(),
+			Value::LangString(_)
+		));
+		assert!(warnings.is_empty());
+
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"Hello",
+			"http://www.w3.org/1999/02/22-rdf-syntax-ns#dirLangString",
+		);
+		match literal_to_value(&mut vocabulary, lit, options).unwrap() {
+			Value::Literal(Literal::String(s), None) => assert_eq!(s.as_str(), "Hello"),
+			other => panic!("expected a plain string, found {other:?}"),
+		}
+		assert_eq!(
+			warnings.take(),
+			[Warning::DroppedDatatype {
+				datatype: "http://www.w3.org/1999/02/22-rdf-syntax-ns#dirLangString".to_owned()
+			}]
+		)
+	}
+
+	#[test]
+	fn malformed_language_tag() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let lit = i18n_literal(
+			&mut vocabulary,
+			"Hello",
+			"https://www.w3.org/ns/i18n#en--us_ltr",
+		);
+
+		match literal_to_value(&mut vocabulary, lit, Options::default()) {
+			Err(Error::InvalidLanguageTag { tag }) => assert_eq!(tag, "en--us"),
+			other => panic!("unexpected result: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn empty_language_tag() {
+		match super::lang_string::<IriIndex>("Hello".to_owned(), Some(""), None).unwrap() {
+			Value::Literal(Literal::String(s), None) => assert_eq!(s.as_str(), "Hello"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+
+		match super::lang_string::<IriIndex>("Hello".to_owned(), Some(""), Some(Direction::Ltr))
+			.unwrap()
+		{
+			Value::LangString(s) => {
+				assert_eq!(s.language(), None);
+				assert_eq!(s.direction(), Some(Direction::Ltr))
+			}
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn lang_string_without_direction() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let lit = RdfLiteral::Any(
+			"Hello".to_owned(),
+			LiteralType::LangString("en".parse().unwrap()),
+		);
+		let s = lang_string(&mut vocabulary, lit);
+
+		assert_eq!(s.language().map(|l| l.as_str()), Some("en"));
+		assert_eq!(s.direction(), None)
+	}
+
+	fn xsd_value(value: xsd_types::Value) -> Value<IriIndex> {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		xsd_to_value(&mut vocabulary, value, Options::default()).unwrap()
+	}
+
+	#[test]
+	fn double_as_number() {
+		match xsd_value(xsd_types::Value::Double(1.5e10.into())) {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "1.5E10"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+
+		match xsd_value(xsd_types::Value::Double(0.0.into())) {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "0.0E0"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+
+		match xsd_value(xsd_types::Value::Float(0.5f32.into())) {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "5.0E-1"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn canonical_numbers() {
+		let big: xsd_types::Integer = "1000000000000000000000".parse().unwrap();
+		match xsd_value(xsd_types::Value::Integer(big)) {
+			Value::Literal(Literal::Number(n), None) => {
+				assert_eq!(n.as_str(), "1000000000000000000000")
+			}
+			other => panic!("unexpected value: {other:?}"),
+		}
+
+		match xsd_value(xsd_types::Value::Double(0.0000001.into())) {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "1.0E-7"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn lenient_numbers() {
+		assert_eq!(normalize_number("+1.5").as_deref(), Some("1.5"));
+		assert_eq!(normalize_number(".5").as_deref(), Some("0.5"));
+		assert_eq!(normalize_number("-.5").as_deref(), Some("-0.5"));
+		assert_eq!(normalize_number("5.").as_deref(), Some("5.0"));
+		assert_eq!(normalize_number("1E3").as_deref(), Some("1E3"));
+		assert_eq!(normalize_number("1e-3").as_deref(), Some("1E-3"));
+		assert_eq!(normalize_number("007.50").as_deref(), Some("7.50"));
+
+		for lexical in ["", ".", "+", "1.2.3", "1E", "1E+", "0x10", "INF", "1 "] {
+			assert_eq!(normalize_number(lexical), None, "{lexical:?}")
+		}
+
+		let decimal: xsd_types::Decimal = "+1.5".parse().unwrap();
+		match xsd_value(xsd_types::Value::Decimal(decimal)) {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "1.5"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn non_finite_double_as_typed_string() {
+		for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+			match xsd_value(xsd_types::Value::Double(value.into())) {
+				Value::Literal(Literal::String(_), Some(_)) => (),
+				other => panic!("unexpected value: {other:?}"),
+			}
+		}
+	}
+
+	#[test]
+	fn canonical_json_keys() {
+		let (json, _) =
+			json_syntax::Value::parse_str(r#"{"b": {"d": 1, "c": 2}, "a": [{"f": 3, "e": 4}]}"#)
+				.unwrap();
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let options = Options {
+			canonical_json: true,
+			..Options::default()
+		};
+
+		match literal_to_value(&mut vocabulary, RdfLiteral::Json(json), options).unwrap() {
+			Value::Json(json) => {
+				assert_eq!(
+					json.compact_print().to_string(),
+					r#"{"a":[{"e":4,"f":3}],"b":{"c":2,"d":1}}"#
+				)
+			}
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn custom_datatype() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let mut datatypes = crate::DatatypeRegistry::new();
+		datatypes.insert(
+			iref::IriBuf::new("http://example.org/celsius".to_owned()).unwrap(),
+			|lexical: &str| {
+				let n = json_syntax::NumberBuf::new(lexical.as_bytes().into()).unwrap();
+				Value::Literal(Literal::Number(n), None)
+			},
+		);
+
+		let options = Options {
+			datatypes: Some(&datatypes),
+			..Options::default()
+		};
+
+		let lit = i18n_literal(&mut vocabulary, "21.5", "http://example.org/celsius");
+		match literal_to_value(&mut vocabulary, lit, options).unwrap() {
+			Value::Literal(Literal::Number(n), None) => assert_eq!(n.as_str(), "21.5"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+
+		let lit = i18n_literal(&mut vocabulary, "21.5", "http://example.org/fahrenheit");
+		match literal_to_value(&mut vocabulary, lit, options).unwrap() {
+			Value::Literal(Literal::String(s), Some(_)) => assert_eq!(s.as_str(), "21.5"),
+			other => panic!("unexpected value: {other:?}"),
+		}
+	}
+
+	#[test]
+	fn failing_custom_datatype() {
+		let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+		let mut datatypes = crate::DatatypeRegistry::new();
+		datatypes.insert_fallible(
+			iref::IriBuf::new("http://example.org/celsius".to_owned()).unwrap(),
+			|lexical: &str| {
+				json_syntax::NumberBuf::new(lexical.as_bytes().into())
+					.map(|n| Value::Literal(Literal::Number(n), None))
+					.map_err(|_| "not a number")
+			},
+		);
+
+		let options = Options {
+			datatypes: Some(&datatypes),
+			..Options::default()
+		};
+
+		let lit = i18n_literal(&mut vocabulary, "warm", "http://example.org/celsius");
+		let error = literal_to_value(&mut vocabulary, lit, options).unwrap_err();
+		match &error {
+			Error::CustomConverter {
+				datatype, lexical, ..
+			} => {
+				assert_eq!(datatype, "http://example.org/celsius");
+				assert_eq!(lexical, "warm")
+			}
+			other => panic!("unexpected error: {other:?}"),
+		}
+
+		let source = std::error::Error::source(&error).unwrap();
+		assert_eq!(source.to_string(), "not a number")
 	}
 }
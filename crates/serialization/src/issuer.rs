@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use rdf_types::{BlankId, BlankIdBuf};
+
+/// Blank node identifier issuer.
+///
+/// Issues the identifiers given to anonymous graphs and anonymous nodes
+/// during serialization. Identifiers are numbered with a single counter, so
+/// sharing an issuer between several serialization calls guarantees that the
+/// documents they produce do not collide.
+///
+/// Reserved identifiers, such as the blank node identifiers already used by
+/// the serialized values, are never issued.
+#[derive(Debug, Default, Clone)]
+pub struct BlankIdIssuer {
+	count: usize,
+	reserved: HashSet<BlankIdBuf>,
+}
+
+impl BlankIdIssuer {
+	/// Creates a new issuer.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of identifiers issued so far.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Reserves the given blank node identifier so that it is never issued.
+	pub fn reserve(&mut self, id: &BlankId) {
+		if !self.reserved.contains(id) {
+			self.reserved.insert(id.to_owned());
+		}
+	}
+
+	/// Issues a fresh blank node identifier of the form `_:{prefix}{n}`,
+	/// skipping the reserved identifiers.
+	pub fn issue(&mut self, prefix: &str) -> BlankIdBuf {
+		loop {
+			let label = BlankIdBuf::new(format!("_:{prefix}{}", self.count)).unwrap();
+			self.count += 1;
+
+			if !self.reserved.contains(label.as_blank_id_ref()) {
+				break label;
+			}
+		}
+	}
+}
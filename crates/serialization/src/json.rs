@@ -0,0 +1,167 @@
+use contextual::WithContext;
+use json_ld_core_next::{
+	print::{Options, Print},
+	ExpandedDocument,
+};
+use json_syntax::print::Indent;
+use rdf_types::Vocabulary;
+
+/// JSON output options.
+///
+/// Controls how a serialized document is printed into a JSON string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonOptions {
+	/// Number of spaces used for each indentation level.
+	///
+	/// If `None`, the document is printed compactly, on a single line and
+	/// without any insignificant whitespace.
+	pub indent: Option<u8>,
+
+	/// Print a document made of a single object as a one-element array.
+	///
+	/// If `false`, the object is printed alone. Other documents, including
+	/// the empty document (`[]`), are always printed as arrays.
+	pub wrap_single: bool,
+}
+
+impl Default for JsonOptions {
+	fn default() -> Self {
+		Self::compact()
+	}
+}
+
+impl JsonOptions {
+	/// Compact output options.
+	pub fn compact() -> Self {
+		Self {
+			indent: None,
+			wrap_single: true,
+		}
+	}
+
+	/// Pretty output options, indenting with the given number of spaces.
+	pub fn pretty(indent: u8) -> Self {
+		Self {
+			indent: Some(indent),
+			wrap_single: true,
+		}
+	}
+
+	/// Sets the [`wrap_single`](Self::wrap_single) option.
+	pub fn with_wrap_single(self, wrap_single: bool) -> Self {
+		Self {
+			wrap_single,
+			..self
+		}
+	}
+
+	fn print_options(&self) -> Options {
+		match self.indent {
+			Some(n) => {
+				let mut options = Options::pretty();
+				options.indent = Indent::Spaces(n);
+				options
+			}
+			None => Options::compact(),
+		}
+	}
+}
+
+/// Type that can be printed into a JSON string.
+pub trait ToJsonString {
+	/// IRI type.
+	type Iri;
+
+	/// Blank node identifier type.
+	type BlankId;
+
+	/// Prints this value into a JSON string using the given vocabulary.
+	fn to_json_string_with<V>(&self, vocabulary: &V, options: JsonOptions) -> String
+	where
+		V: Vocabulary<Iri = Self::Iri, BlankId = Self::BlankId>;
+
+	/// Prints this value into a JSON string.
+	fn to_json_string(&self, options: JsonOptions) -> String
+	where
+		(): Vocabulary<Iri = Self::Iri, BlankId = Self::BlankId>,
+	{
+		self.to_json_string_with(&(), options)
+	}
+}
+
+impl<T, B> ToJsonString for ExpandedDocument<T, B> {
+	type Iri = T;
+	type BlankId = B;
+
+	fn to_json_string_with<V>(&self, vocabulary: &V, options: JsonOptions) -> String
+	where
+		V: Vocabulary<Iri = T, BlankId = B>,
+	{
+		match self.objects().first() {
+			Some(object) if !options.wrap_single && self.len() == 1 => object
+				.with(vocabulary)
+				.print_with(options.print_options())
+				.to_string(),
+			_ => self
+				.with(vocabulary)
+				.print_with(options.print_options())
+				.to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::document;
+
+	#[test]
+	fn compact_and_pretty() {
+		let document = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/name": [{ "@value": "Alice" }]
+			}]"#,
+		);
+
+		assert_eq!(
+			document.to_json_string(JsonOptions::compact()),
+			r#"[{"@id":"http://example.org/a","http://example.org/name":[{"@value":"Alice"}]}]"#
+		);
+
+		assert_eq!(
+			document.to_json_string(JsonOptions::pretty(2)),
+			"[\n  {\n    \"@id\": \"http://example.org/a\",\n    \"http://example.org/name\": [\n      {\n        \"@value\": \"Alice\"\n      }\n    ]\n  }\n]"
+		)
+	}
+
+	#[test]
+	fn wrap_single() {
+		let single = document(r#"[{ "@id": "http://example.org/a" }]"#);
+		let multiple =
+			document(r#"[{ "@id": "http://example.org/a" }, { "@id": "http://example.org/b" }]"#);
+		let empty = document("[]");
+
+		let wrapped = JsonOptions::compact();
+		assert_eq!(
+			single.to_json_string(wrapped),
+			r#"[{"@id":"http://example.org/a"}]"#
+		);
+		assert_eq!(
+			multiple.to_json_string(wrapped),
+			r#"[{"@id":"http://example.org/a"},{"@id":"http://example.org/b"}]"#
+		);
+		assert_eq!(empty.to_json_string(wrapped), "[]");
+
+		let unwrapped = JsonOptions::compact().with_wrap_single(false);
+		assert_eq!(
+			single.to_json_string(unwrapped),
+			r#"{"@id":"http://example.org/a"}"#
+		);
+		assert_eq!(
+			multiple.to_json_string(unwrapped),
+			r#"[{"@id":"http://example.org/a"},{"@id":"http://example.org/b"}]"#
+		);
+		assert_eq!(empty.to_json_string(unwrapped), "[]")
+	}
+}
@@ -3,8 +3,13 @@
 //! crate.
 //! The input value can be an RDF dataset, or any type implementing
 //! [`linked_data_next::LinkedData`].
-use std::hash::Hash;
+//!
+//! RDF-star quoted triples are not supported yet. This is blocked on the
+//! linked-data layer, which only surfaces IRIs, blank node identifiers and
+//! literals as terms.
+use std::{fmt, hash::Hash};
 
+use iref::IriBuf;
 use json_ld_core_next::{ExpandedDocument, Node, Object};
 
 use linked_data_next::{rdf_types::Vocabulary, LinkedData, LinkedDataResource, LinkedDataSubject};
@@ -16,23 +21,221 @@ use rdf_types::{
 	Interpretation,
 };
 
+pub mod canonical;
+mod datatype;
 mod expanded;
+mod issuer;
+mod json;
+mod lists;
+mod nquads;
+#[cfg(test)]
+mod test_utils;
+mod warning;
 
-use expanded::SerializeExpandedDocument;
+pub use datatype::{ConverterError, DatatypeRegistry};
+pub use expanded::{
+	serialize_expanded, serialize_expanded_with, serialize_expanded_with_base,
+	serialize_expanded_with_issuer, serialize_expanded_with_options, serialize_indexed_object_with,
+	serialize_indexed_object_with_options, serialize_node_with, serialize_node_with_options,
+	serialize_object_with, serialize_object_with_options, IndexedResource,
+	SerializeExpandedDocument, SerializeExpandedDocumentBuilder,
+};
+pub use issuer::BlankIdIssuer;
+pub use json::{JsonOptions, ToJsonString};
+pub use lists::{fold_lists, fold_lists_with};
+pub use nquads::{
+	from_nquads, from_nquads_reader, to_flattened_nquads, to_flattened_nquads_with, to_nquads,
+	to_nquads_with, NQuadsError,
+};
+pub use warning::{Warning, Warnings};
+
+/// Serialization options.
+#[derive(Debug)]
+pub struct Options<'a, T = IriBuf> {
+	/// Recursively sort the keys of `rdf:JSON` literal objects.
+	///
+	/// This puts `@json` values in the canonical form expected by the
+	/// JSON-LD specification, regardless of the key order produced by the
+	/// RDF layer.
+	pub canonical_json: bool,
+
+	/// Custom datatype converters.
+	///
+	/// Literals whose datatype is registered are converted with the
+	/// associated converter instead of being serialized as typed strings.
+	pub datatypes: Option<&'a DatatypeRegistry<T>>,
+
+	/// Handling of literals typed as language strings without a language.
+	pub language_datatype: LanguageDatatypePolicy,
+
+	/// Replace the RDF collections of serialized documents with list
+	/// objects.
+	///
+	/// See [`fold_lists`] for the collections that are converted.
+	pub fold_lists: bool,
+
+	/// Handling of the node identifiers made relative to the base IRI given
+	/// to [`serialize_expanded_with_base`].
+	pub relative_ids: RelativeIdPolicy,
+
+	/// Warnings collector.
+	///
+	/// If `None`, warnings are ignored.
+	pub warnings: Option<&'a Warnings>,
+}
+
+impl<T> Default for Options<'_, T> {
+	fn default() -> Self {
+		Self {
+			canonical_json: false,
+			datatypes: None,
+			language_datatype: LanguageDatatypePolicy::default(),
+			fold_lists: false,
+			relative_ids: RelativeIdPolicy::default(),
+			warnings: None,
+		}
+	}
+}
+
+impl<T> Clone for Options<'_, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for Options<'_, T> {}
 
-pub use expanded::{serialize_node_with, serialize_object_with};
+impl<T> PartialEq for Options<'_, T> {
+	fn eq(&self, other: &Self) -> bool {
+		fn same<U>(a: Option<&U>, b: Option<&U>) -> bool {
+			match (a, b) {
+				(Some(a), Some(b)) => std::ptr::eq(a, b),
+				(None, None) => true,
+				_ => false,
+			}
+		}
+
+		self.canonical_json == other.canonical_json
+			&& self.language_datatype == other.language_datatype
+			&& self.fold_lists == other.fold_lists
+			&& self.relative_ids == other.relative_ids
+			&& same(self.datatypes, other.datatypes)
+			&& same(self.warnings, other.warnings)
+	}
+}
+
+impl<T> Eq for Options<'_, T> {}
+
+impl<T> Options<'_, T> {
+	/// Records the given warning, if a collector is set.
+	fn warn(&self, warning: Warning) {
+		if let Some(warnings) = self.warnings {
+			warnings.push(warning)
+		}
+	}
+}
+
+/// Handling of literals typed as language strings without a language.
+///
+/// The `rdf:langString` and `rdf:dirLangString` datatypes require a
+/// language tag, hence a literal using them as a plain datatype conflicts
+/// with itself: a JSON-LD value object cannot have both a `@type` and a
+/// `@language` entry. The `i18n` datatypes
+/// (`https://www.w3.org/ns/i18n#{language}_{direction}`) are not affected:
+/// they are the standard encoding of a language and a base direction, and
+/// are always converted into language strings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageDatatypePolicy {
+	/// Keep the lexical form as a plain string and drop the datatype, with a
+	/// [`Warning::DroppedDatatype`].
+	#[default]
+	Normalize,
+
+	/// Fail with [`Error::LanguageDatatype`].
+	Reject,
+}
+
+/// Handling of node identifiers made relative to a base IRI.
+///
+/// A relative reference is not a valid `@id` in expanded form, and fails
+/// [`ExpandedDocument::validate`]. It is only meaningful once the document
+/// is compacted against the same base IRI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeIdPolicy {
+	/// Keep the relative reference as an invalid identifier.
+	#[default]
+	Keep,
+
+	/// Fail with [`Error::InvalidNodeId`].
+	Reject,
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+	#[deprecated = "literal graph names are reported as `Error::InvalidGraphName`"]
 	#[error("invalid graph label")]
 	InvalidGraph,
 
+	#[error("invalid graph name `{term}`: literals cannot name graphs")]
+	InvalidGraphName {
+		/// Lexical form of the offending literal.
+		term: String,
+	},
+
+	#[error("invalid language tag `{tag}`")]
+	InvalidLanguageTag {
+		/// Malformed language tag.
+		tag: String,
+	},
+
+	#[error("literal with datatype `{datatype}` has no language")]
+	LanguageDatatype {
+		/// Datatype of the offending literal.
+		datatype: String,
+	},
+
+	#[deprecated = "literal predicates are reported as `Error::InvalidLiteral`, unresolvable predicates as `Error::UnresolvableIri`"]
 	#[error("invalid predicate")]
 	InvalidPredicate,
 
+	#[deprecated = "literal node identifiers are reported as `Error::InvalidLiteral`"]
 	#[error("invalid node object")]
 	InvalidNode,
 
+	#[error("literal `{lexical}` cannot be used as {position}")]
+	InvalidLiteral {
+		/// Where the literal was found.
+		position: TermPosition,
+
+		/// Lexical form of the offending literal.
+		lexical: String,
+	},
+
+	#[error("invalid node identifier `{value}`")]
+	InvalidNodeId {
+		/// Offending node identifier.
+		value: String,
+	},
+
+	#[error("{position} has no resolvable IRI")]
+	UnresolvableIri {
+		/// Where the term was found.
+		position: TermPosition,
+	},
+
+	#[error("custom converter for `{datatype}` failed on `{lexical}`")]
+	CustomConverter {
+		/// Datatype of the offending literal.
+		datatype: String,
+
+		/// Lexical form of the offending literal.
+		lexical: String,
+
+		/// Error returned by the converter.
+		#[source]
+		error: ConverterError,
+	},
+
 	#[error("reverse properties on lists are not supported")]
 	ListReverseProperty,
 
@@ -40,6 +243,22 @@ pub enum Error {
 	ListInclude,
 }
 
+/// Position of a term in a triple, reported by serialization errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermPosition {
+	Subject,
+	Predicate,
+}
+
+impl fmt::Display for TermPosition {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Subject => write!(f, "subject"),
+			Self::Predicate => write!(f, "predicate"),
+		}
+	}
+}
+
 /// Serialize the given Linked-Data value into a JSON-LD document.
 pub fn serialize(value: &impl LinkedData) -> Result<ExpandedDocument, Error> {
 	serialize_with(&mut (), &mut (), value)
@@ -47,6 +266,10 @@ pub fn serialize(value: &impl LinkedData) -> Result<ExpandedDocument, Error> {
 
 /// Serialize the given Linked-Data value into a JSON-LD document using a
 /// custom vocabulary and interpretation.
+///
+/// Anonymous graphs are serialized without `@id`. See
+/// [`serialize_expanded_with`] to name them with fresh blank node
+/// identifiers.
 pub fn serialize_with<V, I>(
 	vocabulary: &mut V,
 	interpretation: &mut I,
@@ -61,9 +284,35 @@ where
 		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
 		+ ReverseLiteralInterpretation<Literal = V::Literal>,
 {
-	let serializer = SerializeExpandedDocument::new(vocabulary, interpretation);
+	serialize_with_options(vocabulary, interpretation, value, Options::default())
+}
 
-	value.visit(serializer)
+/// Serialize the given Linked-Data value into a JSON-LD document using a
+/// custom vocabulary, interpretation and serialization options.
+///
+/// Anonymous graphs are serialized without `@id`. See
+/// [`serialize_expanded_with_options`] to name them with fresh blank node
+/// identifiers.
+pub fn serialize_with_options<V, I>(
+	vocabulary: &mut V,
+	interpretation: &mut I,
+	value: &impl LinkedData<I, V>,
+	options: Options<V::Iri>,
+) -> Result<ExpandedDocument<V::Iri, V::BlankId>, Error>
+where
+	V: Vocabulary + IriVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	I: Interpretation
+		+ ReverseIriInterpretation<Iri = V::Iri>
+		+ ReverseBlankIdInterpretation<BlankId = V::BlankId>
+		+ ReverseLiteralInterpretation<Literal = V::Literal>,
+{
+	value.visit(
+		SerializeExpandedDocumentBuilder::new()
+			.with_options(options)
+			.build(vocabulary, interpretation),
+	)
 }
 
 /// Serialize the given Linked-Data value into a JSON-LD object.
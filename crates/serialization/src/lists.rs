@@ -0,0 +1,381 @@
+//! RDF collections to list objects conversion.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use iref::Iri;
+use json_ld_core_next::{
+	object::{Graph, List},
+	rdf::{RDF_FIRST, RDF_NIL, RDF_REST},
+	ExpandedDocument, Id, Indexed, IndexedObject, Node, Object,
+};
+use rdf_types::Vocabulary;
+
+/// Replaces the RDF collections of the given document with list objects.
+///
+/// An RDF collection is a chain of blank nodes linked by `rdf:rest`, ending
+/// with `rdf:nil`, each node giving one item with `rdf:first`. A chain
+/// referenced by a property value is replaced with the equivalent `@list`
+/// object provided each of its blank nodes:
+///   - is referenced exactly once,
+///   - has exactly one `rdf:first` and one `rdf:rest` value,
+///   - has no other entry than its `@id`.
+///
+/// Other chains, such as chains whose tail is shared with another chain, are
+/// kept as regular nodes. Other references to `rdf:nil` than through
+/// `rdf:rest` are replaced with empty lists. The default graph and every
+/// named graph are processed separately.
+pub fn fold_lists(document: &mut ExpandedDocument) {
+	fold_lists_with(&(), document)
+}
+
+/// Replaces the RDF collections of the given document with list objects,
+/// using the given vocabulary.
+///
+/// See [`fold_lists`] for more details.
+pub fn fold_lists_with<V: Vocabulary>(
+	vocabulary: &V,
+	document: &mut ExpandedDocument<V::Iri, V::BlankId>,
+) where
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+{
+	let objects = std::mem::take(document).into_objects();
+	*document = fold_graph(vocabulary, objects).into()
+}
+
+/// Collection nodes of a graph, by identifier.
+///
+/// Ordered, so that the leftover nodes are serialized in a reproducible
+/// order.
+type Cells<T, B> = IndexMap<B, Node<T, B>>;
+
+fn fold_graph<V: Vocabulary>(
+	vocabulary: &V,
+	graph: Graph<V::Iri, V::BlankId>,
+) -> Graph<V::Iri, V::BlankId>
+where
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+{
+	let mut references = HashMap::new();
+	for object in &graph {
+		count_references(object, &mut references)
+	}
+
+	let mut cells = IndexMap::new();
+	let mut others = Vec::new();
+	for object in graph {
+		let cell = object
+			.index()
+			.is_none()
+			.then(|| object.as_node())
+			.flatten()
+			.and_then(|node| cell_id(vocabulary, node, &references));
+
+		match cell {
+			Some(id) => {
+				cells.insert(id, object.into_inner().into_node().unwrap());
+			}
+			None => others.push(object),
+		}
+	}
+
+	let mut result = Graph::new();
+	for mut object in others {
+		fold_object(vocabulary, &mut cells, &mut object);
+		result.insert(object);
+	}
+
+	// Collection nodes that were not part of a complete chain.
+	while let Some(id) = cells.keys().next().cloned() {
+		let mut node = cells.swap_remove(&id).unwrap();
+		fold_node(vocabulary, &mut cells, &mut node);
+		result.insert(Indexed::new(Object::node(node), None));
+	}
+
+	result
+}
+
+/// Counts the references to blank nodes in the given object, ignoring
+/// nested graphs.
+fn count_references<T, B: Clone + Eq + Hash>(
+	object: &Object<T, B>,
+	references: &mut HashMap<B, usize>,
+) {
+	match object {
+		Object::Value(_) => (),
+		Object::List(list) => {
+			for item in list.iter() {
+				count_references(item, references)
+			}
+		}
+		Object::Node(node) => count_node_references(node, references),
+	}
+}
+
+fn count_node_references<T, B: Clone + Eq + Hash>(
+	node: &Node<T, B>,
+	references: &mut HashMap<B, usize>,
+) {
+	if node.is_empty() {
+		if let Some(b) = node.id.as_ref().and_then(Id::as_blank) {
+			*references.entry(b.clone()).or_default() += 1
+		}
+	}
+
+	for (_, objects) in node.properties() {
+		for object in objects {
+			count_references(object, references)
+		}
+	}
+
+	if let Some(reverse_properties) = node.reverse_properties_entry() {
+		for (_, nodes) in reverse_properties {
+			for node in nodes {
+				count_node_references(node, references)
+			}
+		}
+	}
+
+	if let Some(included) = node.included_entry() {
+		for node in included {
+			count_node_references(node, references)
+		}
+	}
+}
+
+/// Returns the identifier of the given node if it is a well-formed collection
+/// node.
+fn cell_id<V: Vocabulary>(
+	vocabulary: &V,
+	node: &Node<V::Iri, V::BlankId>,
+	references: &HashMap<V::BlankId, usize>,
+) -> Option<V::BlankId>
+where
+	V::BlankId: Clone + Eq + Hash,
+{
+	let id = node.id.as_ref()?.as_blank()?;
+
+	let well_formed = references.get(id) == Some(&1)
+		&& node.types.is_none()
+		&& node.graph.is_none()
+		&& node.included.is_none()
+		&& node.reverse_properties.is_none()
+		&& node.properties.len() == 2
+		&& node.properties.iter().all(|(prop, objects)| {
+			objects.len() == 1
+				&& (is_iri(vocabulary, prop, RDF_FIRST) || is_iri(vocabulary, prop, RDF_REST))
+		});
+
+	well_formed.then(|| id.clone())
+}
+
+fn is_iri<V: Vocabulary>(vocabulary: &V, id: &Id<V::Iri, V::BlankId>, iri: &Iri) -> bool {
+	id.as_iri().and_then(|i| vocabulary.iri(i)) == Some(iri)
+}
+
+/// Returns the identifier referenced by the given object, if it is a node
+/// reference.
+fn reference<T, B>(object: &Object<T, B>) -> Option<&Id<T, B>> {
+	object.as_node().filter(|node| node.is_empty())?.id.as_ref()
+}
+
+fn fold_object<V: Vocabulary>(
+	vocabulary: &V,
+	cells: &mut Cells<V::Iri, V::BlankId>,
+	object: &mut IndexedObject<V::Iri, V::BlankId>,
+) where
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+{
+	if let Some(list) = take_list(vocabulary, cells, object) {
+		*object.inner_mut() = Object::List(list);
+		return;
+	}
+
+	match object.inner_mut() {
+		Object::Value(_) => (),
+		Object::List(list) => {
+			for item in list.iter_mut() {
+				fold_object(vocabulary, cells, item)
+			}
+		}
+		Object::Node(node) => fold_node(vocabulary, cells, node),
+	}
+}
+
+fn fold_node<V: Vocabulary>(
+	vocabulary: &V,
+	cells: &mut Cells<V::Iri, V::BlankId>,
+	node: &mut Node<V::Iri, V::BlankId>,
+) where
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+{
+	for (prop, objects) in node.properties.iter_mut() {
+		let is_rest = is_iri(vocabulary, prop, RDF_REST);
+		for object in objects.iter_mut() {
+			// Collections kept as regular nodes still end with `rdf:nil`.
+			if is_rest && reference(object).is_some_and(|id| is_iri(vocabulary, id, RDF_NIL)) {
+				continue;
+			}
+
+			fold_object(vocabulary, cells, object)
+		}
+	}
+
+	if let Some(reverse_properties) = &mut node.reverse_properties {
+		for (_, nodes) in reverse_properties.iter_mut() {
+			for node in nodes.iter_mut() {
+				fold_node(vocabulary, cells, node)
+			}
+		}
+	}
+
+	if let Some(included) = node.included.take() {
+		node.included = Some(
+			included
+				.into_iter()
+				.map(|mut node| {
+					fold_node(vocabulary, cells, &mut node);
+					node
+				})
+				.collect(),
+		)
+	}
+
+	if let Some(graph) = node.graph.take() {
+		node.graph = Some(fold_graph(vocabulary, graph))
+	}
+}
+
+/// Removes the collection referenced by the given object from `cells`, and
+/// returns it as a list, if it forms a complete chain.
+fn take_list<V: Vocabulary>(
+	vocabulary: &V,
+	cells: &mut Cells<V::Iri, V::BlankId>,
+	object: &Object<V::Iri, V::BlankId>,
+) -> Option<List<V::Iri, V::BlankId>>
+where
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+{
+	let mut current = reference(object)?;
+	let mut chain = Vec::new();
+
+	while !is_iri(vocabulary, current, RDF_NIL) {
+		let id = current.as_blank()?;
+		if chain.contains(&id) {
+			return None;
+		}
+
+		let cell = cells.get(id)?;
+		chain.push(id);
+
+		let (_, rest) = cell
+			.properties
+			.iter()
+			.find(|(prop, _)| is_iri(vocabulary, prop, RDF_REST))?;
+		current = reference(&rest[0])?;
+	}
+
+	let chain: Vec<_> = chain.into_iter().cloned().collect();
+	let mut items = Vec::with_capacity(chain.len());
+	for id in chain {
+		let cell = cells.swap_remove(&id).unwrap();
+		let first = cell
+			.properties
+			.into_iter()
+			.find_map(|(prop, objects)| {
+				is_iri(vocabulary, &prop, RDF_FIRST).then(|| objects.into_iter().next())
+			})
+			.flatten()
+			.unwrap();
+		items.push(first)
+	}
+
+	for item in &mut items {
+		fold_object(vocabulary, cells, item)
+	}
+
+	Some(List::new(items))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::document;
+
+	#[test]
+	fn convertible_list() {
+		let mut folded = document(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"http://example.org/p": [{ "@id": "_:l0" }],
+					"http://example.org/empty": [{ "@id": "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil" }]
+				},
+				{
+					"@id": "_:l0",
+					"http://www.w3.org/1999/02/22-rdf-syntax-ns#first": [{ "@value": "x" }],
+					"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest": [{ "@id": "_:l1" }]
+				},
+				{
+					"@id": "_:l1",
+					"http://www.w3.org/1999/02/22-rdf-syntax-ns#first": [{ "@id": "http://example.org/b" }],
+					"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest": [{ "@id": "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil" }]
+				}
+			]"#,
+		);
+
+		fold_lists(&mut folded);
+		assert_eq!(
+			folded,
+			document(
+				r#"[{
+					"@id": "http://example.org/a",
+					"http://example.org/p": [{ "@list": [{ "@value": "x" }, { "@id": "http://example.org/b" }] }],
+					"http://example.org/empty": [{ "@list": [] }]
+				}]"#
+			)
+		)
+	}
+
+	#[test]
+	fn shared_tail() {
+		let shared = r#"[
+			{
+				"@id": "http://example.org/a",
+				"http://example.org/p": [{ "@id": "_:a0" }],
+				"http://example.org/q": [{ "@id": "_:b0" }]
+			},
+			{
+				"@id": "_:a0",
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#first": [{ "@value": "a" }],
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest": [{ "@id": "_:tail" }]
+			},
+			{
+				"@id": "_:b0",
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#first": [{ "@value": "b" }],
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest": [{ "@id": "_:tail" }]
+			},
+			{
+				"@id": "_:tail",
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#first": [{ "@value": "c" }],
+				"http://www.w3.org/1999/02/22-rdf-syntax-ns#rest": [{ "@id": "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil" }]
+			}
+		]"#;
+
+		let mut folded = document(shared);
+		fold_lists(&mut folded);
+		assert_eq!(folded, document(shared));
+
+		// Leftover collection nodes come last, in a reproducible order.
+		let ids: Vec<_> = folded
+			.iter()
+			.map(|o| o.as_node().unwrap().id.as_ref().unwrap().as_str())
+			.collect();
+		assert_eq!(ids, ["http://example.org/a", "_:tail", "_:a0", "_:b0"])
+	}
+}
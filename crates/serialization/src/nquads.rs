@@ -0,0 +1,369 @@
+use std::fmt::Write;
+use std::hash::Hash;
+use std::io::BufRead;
+
+use contextual::{DisplayWithContext, WithContext};
+use indexmap::IndexMap;
+use iref::IriBuf;
+use json_ld_core_next::{
+	flattening::ConflictingIndexes,
+	rdf::{Quad, RdfDirection, RDF_JSON, RDF_TYPE},
+	ExpandedDocument, Flatten, Id, Indexed, Node, Object, RdfQuads,
+};
+use json_syntax::Parse as _;
+use linked_data_next::RdfLiteral;
+use nquads_syntax::Parse as _;
+use rdf_types::{
+	vocabulary::{IriVocabularyMut, LiteralVocabularyMut},
+	BlankIdBuf, Generator, Literal, LiteralType, Term, Vocabulary,
+};
+
+use crate::{expanded::literal_to_value, fold_lists, Error, Options};
+
+/// Serialize the given expanded JSON-LD document into N-Quads.
+///
+/// Blank node identifiers are preserved. Nodes without identifiers and list
+/// cells are assigned fresh blank node identifiers.
+pub fn to_nquads(document: &ExpandedDocument) -> String {
+	let mut generator = rdf_types::generator::Blank::new();
+	to_nquads_with(&mut (), &mut generator, document)
+}
+
+/// Serialize the given expanded JSON-LD document into N-Quads using a custom
+/// vocabulary and blank node identifier `generator`.
+///
+/// Lists are encoded with `rdf:first`/`rdf:rest` chains, JSON literals are
+/// emitted as canonical `rdf:JSON` literals, and language-tagged strings with
+/// a direction use the `https://www.w3.org/ns/i18n#` datatype form.
+pub fn to_nquads_with<V, G>(
+	vocabulary: &mut V,
+	generator: &mut G,
+	document: &ExpandedDocument<V::Iri, V::BlankId>,
+) -> String
+where
+	V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut,
+	V::Iri: Clone,
+	V::BlankId: Clone,
+	V::Literal: Clone,
+	Quad<V::Iri, V::BlankId, V::Literal>: DisplayWithContext<V>,
+	G: Generator<V>,
+{
+	let quads: Vec<_> = document
+		.rdf_quads_with(vocabulary, generator, Some(RdfDirection::I18nDatatype))
+		.cloned()
+		.collect();
+
+	let mut result = String::new();
+	for quad in quads {
+		writeln!(result, "{} .", quad.with(&*vocabulary)).unwrap();
+	}
+
+	result
+}
+
+/// Flatten the given expanded JSON-LD document and serialize it into
+/// N-Quads.
+///
+/// The document is flattened before any quad is emitted: nodes without
+/// identifiers are labeled by the flattening algorithm, in order, so the
+/// output is deterministic. List cells are labeled with the same generator,
+/// so the labels never collide.
+pub fn to_flattened_nquads(
+	document: ExpandedDocument,
+) -> Result<String, ConflictingIndexes<IriBuf, BlankIdBuf>> {
+	let mut generator = rdf_types::generator::Blank::new();
+	to_flattened_nquads_with(&mut (), &mut generator, document)
+}
+
+/// Flatten the given expanded JSON-LD document and serialize it into N-Quads
+/// using a custom vocabulary and blank node identifier `generator`.
+pub fn to_flattened_nquads_with<V, G>(
+	vocabulary: &mut V,
+	generator: &mut G,
+	document: ExpandedDocument<V::Iri, V::BlankId>,
+) -> Result<String, ConflictingIndexes<V::Iri, V::BlankId>>
+where
+	V: Vocabulary + IriVocabularyMut + LiteralVocabularyMut,
+	V::Iri: Clone + Eq + Hash,
+	V::BlankId: Clone + Eq + Hash,
+	V::Literal: Clone,
+	Quad<V::Iri, V::BlankId, V::Literal>: DisplayWithContext<V>,
+	G: Generator<V>,
+{
+	let flattened: ExpandedDocument<_, _> = document
+		.flatten_with(vocabulary, &mut *generator, true)?
+		.into_iter()
+		.map(|node| node.map_inner(Object::node))
+		.collect();
+
+	Ok(to_nquads_with(vocabulary, generator, &flattened))
+}
+
+/// N-Quads deserialization error.
+#[derive(Debug, thiserror::Error)]
+pub enum NQuadsError {
+	#[error(transparent)]
+	Io(#[from] std::io::Error),
+
+	#[error("line {line}: {message}")]
+	Syntax {
+		/// Line number, starting from 1.
+		line: usize,
+
+		/// Error message.
+		message: String,
+	},
+
+	#[error("line {line}: {error}")]
+	Literal {
+		/// Line number, starting from 1.
+		line: usize,
+
+		/// Literal conversion error.
+		#[source]
+		error: Error,
+	},
+}
+
+/// Deserialize the given N-Quads document into an expanded JSON-LD
+/// document.
+///
+/// See [`from_nquads_reader`] for more details.
+pub fn from_nquads(input: &str) -> Result<ExpandedDocument, NQuadsError> {
+	from_nquads_reader(input.as_bytes())
+}
+
+/// Deserialize the N-Quads document read from the given `reader` into an
+/// expanded JSON-LD document.
+///
+/// The input is parsed one line at a time. Quads are grouped into nodes by
+/// subject and graph, `rdf:type` statements are turned into node types and
+/// literals are converted into value objects as the serializer does. RDF
+/// collections are finally folded back into list objects (see
+/// [`fold_lists`]).
+pub fn from_nquads_reader(reader: impl BufRead) -> Result<ExpandedDocument, NQuadsError> {
+	let mut graphs: IndexMap<Option<Id>, IndexMap<Id, Node>> = IndexMap::new();
+	graphs.insert(None, IndexMap::new());
+
+	for (i, line) in reader.lines().enumerate() {
+		let line_number = i + 1;
+		let line = line?;
+		let quads = nquads_syntax::Document::parse_str(&line)
+			.map_err(|e| NQuadsError::Syntax {
+				line: line_number,
+				message: e.into_value().to_string(),
+			})?
+			.into_value();
+
+		for quad in quads {
+			let rdf_types::Quad(s, p, o, g) = nquads_syntax::strip_quad(quad.into_value());
+			let graph = g.map(Id::Valid);
+
+			if let Some(name) = &graph {
+				graphs[0]
+					.entry(name.clone())
+					.or_insert_with(|| Node::with_id(name.clone()));
+			}
+
+			let node = graphs
+				.entry(graph)
+				.or_default()
+				.entry(Id::Valid(s.clone()))
+				.or_insert_with(|| Node::with_id(Id::Valid(s)));
+
+			let object = match o {
+				Term::Id(id) if p == RDF_TYPE => {
+					let ty = Id::Valid(id);
+					if !node.types().contains(&ty) {
+						node.types_mut_or_default().push(ty)
+					}
+
+					continue;
+				}
+				Term::Id(id) => Object::node(Node::with_id(Id::Valid(id))),
+				Term::Literal(literal) => {
+					let value = literal_to_value(
+						&mut (),
+						rdf_literal(literal, line_number)?,
+						Options::default(),
+					)
+					.map_err(|error| NQuadsError::Literal {
+						line: line_number,
+						error,
+					})?;
+
+					Object::Value(value)
+				}
+			};
+
+			node.properties_mut()
+				.insert_unique(Id::iri(p), Indexed::new(object, None))
+		}
+	}
+
+	let mut graphs = graphs.into_iter();
+	let (_, mut default_graph) = graphs.next().unwrap();
+	for (name, nodes) in graphs {
+		let graph = nodes
+			.into_values()
+			.map(|node| Indexed::new(Object::node(node), None))
+			.collect();
+		default_graph[&name.unwrap()].set_graph_entry(Some(graph))
+	}
+
+	let mut document: ExpandedDocument = default_graph
+		.into_values()
+		.map(|node| Indexed::new(Object::node(node), None))
+		.collect();
+	fold_lists(&mut document);
+	Ok(document)
+}
+
+/// Converts a parsed literal, decoding `rdf:JSON` literals.
+fn rdf_literal(literal: Literal, line: usize) -> Result<RdfLiteral<()>, NQuadsError> {
+	match literal.type_ {
+		LiteralType::Any(ty) if ty == RDF_JSON => {
+			let (json, _) =
+				json_syntax::Value::parse_str(&literal.value).map_err(|e| NQuadsError::Syntax {
+					line,
+					message: format!("invalid JSON literal: {e}"),
+				})?;
+
+			Ok(RdfLiteral::Json(json))
+		}
+		ty => Ok(RdfLiteral::Any(literal.value, ty)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_utils::document;
+
+	fn nquads(json: &str) -> String {
+		to_nquads(&document(json))
+	}
+
+	#[test]
+	fn list() {
+		let output = nquads(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/p": [{ "@list": [{ "@value": "x" }, { "@value": "y" }] }]
+			}]"#,
+		);
+
+		let lines: Vec<_> = output.lines().collect();
+		assert_eq!(lines.len(), 5);
+		assert!(lines[0].starts_with("<http://example.org/a> <http://example.org/p> _:"));
+		assert_eq!(
+			lines
+				.iter()
+				.filter(|l| l.contains("<http://www.w3.org/1999/02/22-rdf-syntax-ns#first>"))
+				.count(),
+			2
+		);
+		assert!(output.contains(
+			"<http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> ."
+		))
+	}
+
+	#[test]
+	fn lang_string() {
+		assert_eq!(
+			nquads(
+				r#"[{
+					"@id": "_:a",
+					"http://example.org/p": [
+						{ "@value": "hello", "@language": "en" },
+						{ "@value": "salut", "@language": "fr", "@direction": "ltr" }
+					]
+				}]"#
+			),
+			"_:a <http://example.org/p> \"hello\"@en .\n\
+			_:a <http://example.org/p> \"salut\"^^<https://www.w3.org/ns/i18n#fr_ltr> .\n"
+		)
+	}
+
+	#[test]
+	fn typed_number() {
+		assert_eq!(
+			nquads(
+				r#"[{
+					"@id": "http://example.org/a",
+					"http://example.org/p": [{ "@value": 12 }, { "@value": 1.5 }]
+				}]"#
+			),
+			"<http://example.org/a> <http://example.org/p> \"12\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+			<http://example.org/a> <http://example.org/p> \"1.5E0\"^^<http://www.w3.org/2001/XMLSchema#double> .\n"
+		)
+	}
+
+	#[test]
+	fn flattened() {
+		let document = document(
+			r#"[{
+				"@id": "http://example.org/a",
+				"http://example.org/knows": [{
+					"http://example.org/name": [{ "@value": "Bob" }]
+				}],
+				"http://example.org/p": [{ "@list": [{ "@value": "x" }] }]
+			}]"#,
+		);
+
+		assert_eq!(
+			to_flattened_nquads(document).unwrap(),
+			"_:0 <http://example.org/name> \"Bob\" .\n\
+			<http://example.org/a> <http://example.org/knows> _:0 .\n\
+			<http://example.org/a> <http://example.org/p> _:1 .\n\
+			_:1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"x\" .\n\
+			_:1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n"
+		)
+	}
+
+	#[test]
+	fn round_trip() {
+		let input = "<http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n\
+			<http://example.org/a> <http://example.org/name> \"Alice\"@en .\n\
+			<http://example.org/a> <http://example.org/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+			<http://example.org/a> <http://example.org/knows> _:b .\n\
+			<http://example.org/a> <http://example.org/p> _:l0 .\n\
+			_:l0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"x\" .\n\
+			_:l0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l1 .\n\
+			_:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"y\" .\n\
+			_:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n\
+			# comment\n\
+			\n\
+			_:b <http://example.org/name> \"Bob\" <http://example.org/g> .\n";
+
+		let document = from_nquads(input).unwrap();
+		assert_eq!(document.len(), 2);
+
+		let a = document
+			.iter()
+			.find_map(|object| object.as_node().filter(|node| node.as_iri().is_some()))
+			.unwrap();
+		let p = Id::iri(IriBuf::new("http://example.org/p".to_owned()).unwrap());
+		assert!(a.get_any(&p).unwrap().is_list());
+
+		let mut expected: Vec<_> = input
+			.lines()
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.map(|line| line.replace("_:l0", "_:0").replace("_:l1", "_:1"))
+			.collect();
+		let mut output: Vec<_> = to_nquads(&document).lines().map(str::to_owned).collect();
+		expected.sort();
+		output.sort();
+		assert_eq!(output, expected)
+	}
+
+	#[test]
+	fn syntax_error() {
+		assert!(matches!(
+			from_nquads(
+				"<http://example.org/a> <http://example.org/p> \"x\" .\n<http://example.org/a> .\n"
+			),
+			Err(NQuadsError::Syntax { line: 2, .. })
+		))
+	}
+}
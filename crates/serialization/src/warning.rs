@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+
+/// Serialization warning.
+///
+/// Non-fatal issue found while serializing, after which the input was
+/// coerced into a valid JSON-LD value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Warning {
+	/// The language string datatype of a literal without language was
+	/// dropped.
+	#[error("dropped datatype `{datatype}` of a literal without language")]
+	DroppedDatatype {
+		/// Dropped datatype.
+		datatype: String,
+	},
+
+	/// A numeric literal could not be converted into a JSON number, and was
+	/// kept as a typed string.
+	#[error("numeric literal `{lexical}` of type `{datatype}` kept as a string")]
+	CoercedLiteral {
+		/// Lexical form of the literal.
+		lexical: String,
+
+		/// Datatype of the literal.
+		datatype: String,
+	},
+}
+
+/// Serialization warnings collector.
+///
+/// The collector is shared by reference with the serializer through the
+/// [`Options`](crate::Options), hence the interior mutability.
+#[derive(Debug, Default)]
+pub struct Warnings(RefCell<Vec<Warning>>);
+
+impl Warnings {
+	/// Creates an empty collector.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records the given warning.
+	pub fn push(&self, warning: Warning) {
+		self.0.borrow_mut().push(warning)
+	}
+
+	/// Returns the number of recorded warnings.
+	pub fn len(&self) -> usize {
+		self.0.borrow().len()
+	}
+
+	/// Checks if no warning was recorded.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Removes and returns the recorded warnings.
+	pub fn take(&self) -> Vec<Warning> {
+		self.0.take()
+	}
+
+	/// Returns the recorded warnings.
+	pub fn into_vec(self) -> Vec<Warning> {
+		self.0.into_inner()
+	}
+}
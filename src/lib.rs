@@ -311,7 +311,7 @@ pub use json_ld_syntax_next as syntax;
 
 pub use compaction::Compact;
 pub use context_processing::Process;
-pub use expansion::Expand;
+pub use expansion::{Expand, FromJsonStr};
 
 mod processor;
 pub use processor::*;
@@ -124,6 +124,7 @@ impl<I> Options<I> {
 			compact_to_relative: self.compact_to_relative,
 			compact_arrays: self.compact_arrays,
 			ordered: self.ordered,
+			..compaction::Options::default()
 		}
 	}
 }